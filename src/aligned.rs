@@ -0,0 +1,59 @@
+//! Wrappers that raise the alignment of a string buffer, so that a
+//! [zstr](crate::zstr) embedded in a struct can be handed to DMA
+//! controllers or memory-mapped peripherals with alignment requirements.
+//! Since `zstr<N>` is `repr(transparent)` over `[u8; N]`, the first
+//! byte of the string is at the start of the aligned wrapper.
+//! ```
+//!  use no_std_strings::{zstr, Align4, Align8};
+//!  let mut rx = Align8(zstr::<13>::from("AT"));
+//!  assert_eq!(core::mem::align_of_val(&rx), 8);
+//!  assert_eq!(rx.as_ptr() as usize % 8, 0);
+//!  rx.push("+GMR");  // derefs to the wrapped zstr
+//!  assert_eq!(*rx, "AT+GMR");
+//!  let tx: Align4<zstr<32>> = Align4::from(zstr::from("ping"));
+//!  assert_eq!(tx.len(), 4);
+//! ```
+
+macro_rules! generate_aligned {
+    ($name: ident, $align: literal) => {
+        #[doc = concat!("wrapper aligning its contents to ", stringify!($align), " bytes; derefs to the inner value")]
+        #[repr(C, align($align))]
+        #[derive(Copy, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name<T>(pub T);
+
+        impl<T> $name<T> {
+            /// returns the wrapped value
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+        impl<T> From<T> for $name<T> {
+            fn from(t: T) -> Self {
+                $name(t)
+            }
+        }
+        impl<T> core::ops::Deref for $name<T> {
+            type Target = T;
+            fn deref(&self) -> &T {
+                &self.0
+            }
+        }
+        impl<T> core::ops::DerefMut for $name<T> {
+            fn deref_mut(&mut self) -> &mut T {
+                &mut self.0
+            }
+        }
+        impl<T: core::fmt::Display> core::fmt::Display for $name<T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+        impl<T: core::fmt::Debug> core::fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+generate_aligned!(Align4, 4);
+generate_aligned!(Align8, 8);
@@ -0,0 +1,93 @@
+//! Removal of ANSI escape sequences.
+
+use crate::{tstr, zstr};
+
+// end of the parameter, intermediate and final bytes of a control
+// sequence starting at j
+fn csi_end(b: &[u8], mut j: usize) -> usize {
+    while j < b.len() && (0x30..=0x3F).contains(&b[j]) {
+        j += 1;
+    }
+    while j < b.len() && (0x20..=0x2F).contains(&b[j]) {
+        j += 1;
+    }
+    if j < b.len() && (0x40..=0x7E).contains(&b[j]) {
+        j += 1;
+    }
+    j
+}
+
+// end of a control string (OSC, DCS, ...) starting at j, terminated by
+// BEL or by ESC \
+fn string_end(b: &[u8], mut j: usize) -> usize {
+    while j < b.len() {
+        match (b[j], b.get(j + 1)) {
+            (0x07, _) => return j + 1,
+            (0x1B, Some(b'\\')) => return j + 2,
+            _ => j += 1,
+        }
+    }
+    j
+}
+
+/// calls `out` with each run of text in s that lies outside of escape
+/// sequences: CSI sequences (including 8-bit CSI), control strings such
+/// as OSC, and two-character escapes such as `ESC ( B`.
+pub(crate) fn strip_ansi(s: &str, mut out: impl FnMut(&str)) {
+    let b = s.as_bytes();
+    let (mut i, mut lit) = (0, 0);
+    while i < b.len() {
+        // escapes only advance over ascii bytes, so every cut is at a
+        // character boundary
+        let end = match (b[i], b.get(i + 1)) {
+            (0x1B, Some(b'[')) | (0xC2, Some(0x9B)) => csi_end(b, i + 2),
+            (0x1B, Some(b']' | b'P' | b'X' | b'^' | b'_')) => string_end(b, i + 2),
+            (0x1B, _) => {
+                let mut j = i + 1;
+                while j < b.len() && (0x20..=0x2F).contains(&b[j]) {
+                    j += 1;
+                }
+                if j < b.len() && (0x30..=0x7E).contains(&b[j]) { j + 1 } else { j }
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        out(&s[lit..i]);
+        i = end;
+        lit = end;
+    }
+    out(&s[lit..]);
+} //strip_ansi
+
+impl<const N: usize> zstr<N> {
+    /// returns a copy of the string with ANSI escape sequences (colors,
+    /// cursor movement, window titles) removed, so that its length
+    /// reflects the displayed text.
+    /// ```
+    ///  use no_std_strings::zstr;
+    ///  let s = zstr::<32>::from("\x1b[1;31mERR\x1b[0m: disk");
+    ///  assert_eq!(s.strip_ansi(), "ERR: disk");
+    /// ```
+    pub fn strip_ansi(&self) -> zstr<N> {
+        let mut z = zstr::<N>::new();
+        strip_ansi(self.to_str(), |t| { z.push(t); });
+        z
+    }
+}
+
+impl<const N: usize> tstr<N> {
+    /// returns a copy of the string with ANSI escape sequences (colors,
+    /// cursor movement, window titles) removed.
+    /// ```
+    ///  use no_std_strings::str64;
+    ///  let s = str64::from("\x1b]0;title\x07\x1b[2K\x1b[32mok\x1b[m λ");
+    ///  assert_eq!(s.strip_ansi(), "ok λ");
+    /// ```
+    pub fn strip_ansi(&self) -> tstr<N> {
+        let mut t = tstr::<N>::new();
+        strip_ansi(self.to_str(), |s| { t.push(s); });
+        t
+    }
+}
@@ -0,0 +1,68 @@
+//! A fixed-buffer bump allocator for short-lived strings.
+
+use core::cell::{Cell, UnsafeCell};
+
+/// Bump allocator over a fixed buffer of `BYTES` bytes that hands out
+/// `&str` slices sharing the lifetime of the arena.  Strings are never
+/// freed individually; [StrArena::reset] releases all of them at once,
+/// which requires that no slices are still borrowed.  Useful for building
+/// many strings per frame (as when rendering a UI) without fragmentation.
+/// ```
+///  use no_std_strings::{StrArena, str_format, str16};
+///  let mut arena = StrArena::<32>::new();
+///  let a = arena.alloc_str("hello").unwrap();
+///  let b = arena.alloc_str(&str_format!(str16, "x={}", 42)).unwrap();
+///  assert_eq!((a, b), ("hello", "x=42"));
+///  assert_eq!(arena.used(), 9);
+///  assert!(arena.alloc_str("this string does not fit").is_none());
+///  arena.reset();
+///  assert_eq!(arena.remaining(), 32);
+/// ```
+pub struct StrArena<const BYTES: usize> {
+    buf: UnsafeCell<[u8; BYTES]>,
+    used: Cell<usize>,
+}
+
+impl<const BYTES: usize> StrArena<BYTES> {
+    /// creates an empty arena
+    pub const fn new() -> Self {
+        StrArena { buf: UnsafeCell::new([0; BYTES]), used: Cell::new(0) }
+    }
+
+    /// copies s into the arena and returns the copy, or None if there is
+    /// not enough space left
+    pub fn alloc_str(&self, s: &str) -> Option<&str> {
+        let start = self.used.get();
+        let end = start.checked_add(s.len()).filter(|e| *e <= BYTES)?;
+        // Safety: bytes from `used` onward have never been handed out, and
+        // handed out bytes are not written again until reset, which
+        // requires &mut self and therefore that no slices are borrowed.
+        unsafe {
+            let p = (self.buf.get() as *mut u8).add(start);
+            core::ptr::copy_nonoverlapping(s.as_ptr(), p, s.len());
+            self.used.set(end);
+            Some(core::str::from_utf8_unchecked(core::slice::from_raw_parts(p, s.len())))
+        }
+    } //alloc_str
+
+    /// releases all strings allocated from the arena
+    pub fn reset(&mut self) {
+        self.used.set(0);
+    }
+
+    /// number of bytes allocated
+    pub fn used(&self) -> usize {
+        self.used.get()
+    }
+
+    /// number of bytes still available
+    pub fn remaining(&self) -> usize {
+        BYTES - self.used.get()
+    }
+}
+
+impl<const BYTES: usize> Default for StrArena<BYTES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,138 @@
+//! Shell-like splitting of command lines into arguments, for serial
+//! consoles and other embedded command interpreters.
+
+use crate::{tstr, zstr};
+use core::fmt::Write;
+use core::marker::PhantomData;
+
+/// Errors reported by [SplitArgs]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ArgsError {
+    /// a quote opened at the given byte position is never closed
+    UnterminatedQuote(usize),
+    /// the line ends with an unescaped backslash
+    TrailingBackslash,
+    /// an argument does not fit in the token type
+    ExceedsCapacity,
+}
+
+impl core::fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ArgsError::UnterminatedQuote(i) => write!(f, "unterminated quote at byte {}", i),
+            ArgsError::TrailingBackslash => f.write_str("trailing backslash"),
+            ArgsError::ExceedsCapacity => f.write_str("argument exceeds capacity"),
+        }
+    }
+}
+
+/// Iterator over the arguments of a command line, returned by
+/// `split_args()` on the string types or created with [SplitArgs::new].
+/// Arguments are separated by whitespace.  Outside of quotes a backslash
+/// escapes the next character.  Inside single quotes every character is
+/// literal; inside double quotes a backslash only escapes `"` and `\`.
+/// After an error is returned the iterator is exhausted.
+pub struct SplitArgs<'a, T> {
+    s: &'a str,
+    pos: usize,
+    _token: PhantomData<T>,
+}
+
+impl<'a, T: Default + Write> SplitArgs<'a, T> {
+    /// splits s into tokens of type T
+    pub fn new(s: &'a str) -> Self {
+        SplitArgs { s, pos: 0, _token: PhantomData }
+    }
+
+    fn token(&mut self) -> Result<T, ArgsError> {
+        let mut t = T::default();
+        let mut quote: Option<(char, usize)> = None;
+        let mut chars = self.s[self.pos..].char_indices();
+        let base = self.pos;
+        let mut end = self.s.len();
+        while let Some((i, c)) = chars.next() {
+            let r = match (quote, c) {
+                (None, c) if c.is_whitespace() => {
+                    end = base + i;
+                    break;
+                }
+                (None, '\'' | '"') => {
+                    quote = Some((c, base + i));
+                    Ok(())
+                }
+                (Some((q, _)), c) if c == q => {
+                    quote = None;
+                    Ok(())
+                }
+                (Some(('\'', _)), c) => t.write_char(c),
+                (_, '\\') => match chars.next() {
+                    None => return Err(ArgsError::TrailingBackslash),
+                    Some((_, e)) if quote.is_none() || e == '"' || e == '\\' => t.write_char(e),
+                    Some((_, e)) => t.write_char('\\').and_then(|_| t.write_char(e)),
+                },
+                (_, c) => t.write_char(c),
+            };
+            r.map_err(|_| ArgsError::ExceedsCapacity)?;
+        } //while
+        if let Some((_, at)) = quote {
+            return Err(ArgsError::UnterminatedQuote(at));
+        }
+        self.pos = end;
+        Ok(t)
+    } //token
+}
+
+impl<T: Default + Write> Iterator for SplitArgs<'_, T> {
+    type Item = Result<T, ArgsError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.s[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+        if self.pos == self.s.len() {
+            return None;
+        }
+        let r = self.token();
+        if r.is_err() {
+            self.pos = self.s.len();
+        }
+        Some(r)
+    }
+}
+
+impl<const N: usize> zstr<N> {
+    /// splits the string into shell-like arguments, see [SplitArgs].
+    /// Each argument is at most as long as the string itself, so tokens
+    /// of the same type cannot exceed capacity.
+    /// ```
+    ///  use no_std_strings::{zstr, ArgsError};
+    ///  let line = zstr::<64>::from(r#"cp "my file.txt" it\'s 'a\b'"#);
+    ///  let mut args = line.split_args();
+    ///  assert_eq!(args.next(), Some(Ok(zstr::from("cp"))));
+    ///  assert_eq!(args.next(), Some(Ok(zstr::from("my file.txt"))));
+    ///  assert_eq!(args.next(), Some(Ok(zstr::from("it's"))));
+    ///  assert_eq!(args.next(), Some(Ok(zstr::from(r"a\b"))));
+    ///  assert_eq!(args.next(), None);
+    ///  let bad = zstr::<16>::from("echo \"oops");
+    ///  let r: Result<Vec<_>, _> = bad.split_args().collect();
+    ///  assert_eq!(r, Err(ArgsError::UnterminatedQuote(5)));
+    /// ```
+    pub fn split_args(&self) -> SplitArgs<'_, zstr<N>> {
+        SplitArgs::new(self.to_str())
+    }
+}
+
+impl<const N: usize> tstr<N> {
+    /// splits the string into shell-like arguments, see [SplitArgs].
+    /// Each argument is at most as long as the string itself, so tokens
+    /// of the same type cannot exceed capacity.
+    /// ```
+    ///  use no_std_strings::{str32, ArgsError};
+    ///  let line = str32::from(r#"set  name "a \"b\"" """#);
+    ///  let args: Vec<_> = line.split_args().map(Result::unwrap).collect();
+    ///  assert_eq!(args, ["set", "name", "a \"b\"", ""]);
+    ///  let bad = str32::from(r"ls dir\");
+    ///  assert_eq!(bad.split_args().last(), Some(Err(ArgsError::TrailingBackslash)));
+    /// ```
+    pub fn split_args(&self) -> SplitArgs<'_, tstr<N>> {
+        SplitArgs::new(self.to_str())
+    }
+}
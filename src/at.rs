@@ -0,0 +1,196 @@
+//! Building AT commands for cellular and Wi-Fi modems, and splitting
+//! their `+CMD: ...` responses into fields.  String arguments are quoted
+//! and escaped the way ESP-AT and most modem firmware expect: a
+//! backslash is placed before `"`, `,` and `\`.
+//! ```
+//!  use no_std_strings::str64;
+//!  use no_std_strings::at::{split_response, Command, Field};
+//!  let cmd: str64 = Command::new("+CWJAP").arg_str("home").arg_str("p,w").finish().unwrap();
+//!  assert_eq!(cmd, r#"AT+CWJAP="home","p\,w""#);
+//!  let (name, mut fields) = split_response(r#"+CWJAP: "home","a\"b",6,-52"#).unwrap();
+//!  assert_eq!(name, "+CWJAP");
+//!  assert_eq!(fields.next(), Some(Field::Str("home")));
+//!  assert_eq!(fields.next().unwrap().unescape::<16>().unwrap(), "a\"b");
+//!  assert_eq!(fields.next().and_then(|f| f.as_int()), Some(6));
+//!  assert_eq!(fields.next().and_then(|f| f.as_int()), Some(-52));
+//!  assert_eq!(fields.next(), None);
+//! ```
+
+use crate::{tstr, CapacityError};
+use core::fmt::Write;
+
+/// Builder of an AT command in a fixed string of type T, such as
+/// `AT+CWJAP="ssid","pw"`.  The `\r\n` terminator is not included.
+/// Overflow is remembered and reported by [Command::finish].
+pub struct Command<T> {
+    buf: T,
+    args: usize,
+    overflow: bool,
+}
+
+impl<T: Default + Write> Command<T> {
+    /// starts a command: "AT" followed by name, e.g. `+CSQ` or `E0`
+    pub fn new(name: &str) -> Self {
+        let mut c = Command { buf: T::default(), args: 0, overflow: false };
+        c.write(|w| write!(w, "AT{}", name));
+        c
+    }
+
+    fn write(&mut self, f: impl FnOnce(&mut T) -> core::fmt::Result) {
+        if !self.overflow && f(&mut self.buf).is_err() {
+            self.overflow = true;
+        }
+    }
+
+    fn sep(&mut self) {
+        let s = if self.args == 0 { "=" } else { "," };
+        self.args += 1;
+        self.write(|w| w.write_str(s));
+    }
+
+    /// appends a quoted and escaped string argument
+    pub fn arg_str(mut self, s: &str) -> Self {
+        self.sep();
+        self.write(|w| {
+            w.write_char('"')?;
+            for c in s.chars() {
+                if matches!(c, '"' | ',' | '\\') {
+                    w.write_char('\\')?;
+                }
+                w.write_char(c)?;
+            }
+            w.write_char('"')
+        });
+        self
+    }
+
+    /// appends an integer argument
+    pub fn arg_int(mut self, i: i64) -> Self {
+        self.sep();
+        self.write(|w| write!(w, "{}", i));
+        self
+    }
+
+    /// appends an argument as is, without quotes or escaping
+    pub fn arg_raw(mut self, s: &str) -> Self {
+        self.sep();
+        self.write(|w| w.write_str(s));
+        self
+    }
+
+    /// appends `?`, turning the command into a query such as `AT+CWMODE?`
+    pub fn query(mut self) -> Self {
+        self.write(|w| w.write_str("?"));
+        self
+    }
+
+    /// returns the command, or [CapacityError] if it did not fit
+    pub fn finish(self) -> Result<T, CapacityError> {
+        if self.overflow { Err(CapacityError) } else { Ok(self.buf) }
+    }
+} //impl Command
+
+/// A field of a modem response
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Field<'a> {
+    /// a quoted string, without the quotes and still escaped
+    Str(&'a str),
+    /// an unquoted field, trimmed of whitespace, possibly empty
+    Raw(&'a str),
+}
+
+impl<'a> Field<'a> {
+    /// the text of the field, still escaped if quoted
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            Field::Str(s) | Field::Raw(s) => s,
+        }
+    }
+
+    /// parses an unquoted field as an integer
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Field::Raw(s) => s.parse().ok(),
+            Field::Str(_) => None,
+        }
+    }
+
+    /// copies the field into a tstr\<M\>, removing escapes from quoted
+    /// strings
+    pub fn unescape<const M: usize>(&self) -> Result<tstr<M>, CapacityError> {
+        let mut t = tstr::<M>::new();
+        let mut esc = false;
+        for c in self.as_str().chars() {
+            if esc || c != '\\' || matches!(self, Field::Raw(_)) {
+                t.write_char(c).map_err(|_| CapacityError)?;
+                esc = false;
+            } else {
+                esc = true;
+            }
+        }
+        Ok(t)
+    } //unescape
+} //impl Field
+
+/// Iterator over the comma separated fields of a response, returned by
+/// [split_response] or created with [Fields::new].  Commas inside
+/// quoted strings do not separate fields.
+#[derive(Clone, Debug)]
+pub struct Fields<'a> {
+    rest: Option<&'a str>,
+}
+
+impl<'a> Fields<'a> {
+    /// splits s into fields
+    pub fn new(s: &'a str) -> Self {
+        Fields { rest: Some(s) }
+    }
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = Field<'a>;
+    fn next(&mut self) -> Option<Field<'a>> {
+        let s = self.rest?.trim_start();
+        if let Some(q) = s.strip_prefix('"') {
+            let mut esc = false;
+            let end = q.char_indices().find(|&(_, c)| {
+                let close = !esc && c == '"';
+                esc = !esc && c == '\\';
+                close
+            });
+            // an unterminated string extends to the end of the line
+            let (inner, after) = match end {
+                Some((i, _)) => (&q[..i], &q[i + 1..]),
+                None => (q, ""),
+            };
+            self.rest = after.split_once(',').map(|(_, r)| r);
+            Some(Field::Str(inner))
+        } else {
+            let (field, rest) = match s.split_once(',') {
+                Some((f, r)) => (f, Some(r)),
+                None => (s, None),
+            };
+            self.rest = rest;
+            Some(Field::Raw(field.trim()))
+        }
+    } //next
+}
+
+/// splits a response line such as `+CSQ: 23,99` into its name and
+/// fields.  Returns None if the line does not start with `+` or has no
+/// `:`.  Trailing `\r\n` should be removed beforehand.
+/// ```
+///  use no_std_strings::at::{split_response, Field};
+///  let (name, fields) = split_response("+CGPADDR: 1,\"10.0.0.2\",").unwrap();
+///  assert_eq!(name, "+CGPADDR");
+///  let v: Vec<_> = fields.collect();
+///  assert_eq!(v, [Field::Raw("1"), Field::Str("10.0.0.2"), Field::Raw("")]);
+///  assert!(split_response("OK").is_none());
+/// ```
+pub fn split_response(line: &str) -> Option<(&str, Fields<'_>)> {
+    if !line.starts_with('+') {
+        return None;
+    }
+    let (name, rest) = line.split_once(':')?;
+    Some((name, Fields::new(rest)))
+}
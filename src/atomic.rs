@@ -0,0 +1,78 @@
+//! Lock-free shared short strings, such as a status word updated by an
+//! interrupt handler and read by the main loop.
+
+use crate::str8;
+#[cfg(not(feature = "portable-atomic"))]
+use core::sync::atomic::AtomicU64;
+#[cfg(feature = "portable-atomic")]
+use portable_atomic::AtomicU64;
+use core::sync::atomic::Ordering;
+
+/// A [str8] that can be shared between threads and interrupt handlers,
+/// stored in a single 64-bit atomic word.  It can be placed in a
+/// `static` since [AtomicStr8::new] is const.  On targets without native
+/// 64-bit atomics `--features portable-atomic` is required; on
+/// single-core parts such as Cortex-M0, portable-atomic must also be
+/// configured with its `critical-section` feature or the
+/// `portable_atomic_unsafe_assume_single_core` cfg.
+/// ```
+///  use no_std_strings::{str8, AtomicStr8};
+///  use core::sync::atomic::Ordering;
+///  static STATUS: AtomicStr8 = AtomicStr8::new(str8::const_make("boot"));
+///  STATUS.store(str8::from("ready"), Ordering::Release);
+///  assert_eq!(STATUS.load(Ordering::Acquire), "ready");
+///  let old = STATUS.swap(str8::from("busy"), Ordering::AcqRel);
+///  assert_eq!(old, "ready");
+///  let r = STATUS.compare_exchange(str8::from("idle"), str8::from("x"),
+///                                  Ordering::AcqRel, Ordering::Acquire);
+///  assert_eq!(r, Err(str8::from("busy")));
+/// ```
+pub struct AtomicStr8(AtomicU64);
+
+impl AtomicStr8 {
+    /// creates a new atomic string
+    pub const fn new(s: str8) -> Self {
+        AtomicStr8(AtomicU64::new(s.to_u64()))
+    }
+
+    /// loads the string
+    pub fn load(&self, order: Ordering) -> str8 {
+        str8::from_u64(self.0.load(order))
+    }
+
+    /// stores the string
+    pub fn store(&self, s: str8, order: Ordering) {
+        self.0.store(s.to_u64(), order)
+    }
+
+    /// stores the string, returning the previous one
+    pub fn swap(&self, s: str8, order: Ordering) -> str8 {
+        str8::from_u64(self.0.swap(s.to_u64(), order))
+    }
+
+    /// stores `new` if the current string equals `current`.  Returns the
+    /// previous string, as Ok if the store happened and as Err otherwise.
+    pub fn compare_exchange(&self, current: str8, new: str8, success: Ordering, failure: Ordering) -> Result<str8, str8> {
+        self.0
+            .compare_exchange(current.to_u64(), new.to_u64(), success, failure)
+            .map(str8::from_u64)
+            .map_err(str8::from_u64)
+    }
+
+    /// returns the string, given exclusive access
+    pub fn into_inner(self) -> str8 {
+        str8::from_u64(self.0.into_inner())
+    }
+} //impl AtomicStr8
+
+impl Default for AtomicStr8 {
+    fn default() -> Self {
+        Self::new(str8::new())
+    }
+}
+
+impl core::fmt::Debug for AtomicStr8 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.load(Ordering::Relaxed).fmt(f)
+    }
+}
@@ -0,0 +1,101 @@
+//! Base32 encoding and decoding (RFC 4648), as needed for TOTP secrets
+//! and provisioning keys where Base64 is not accepted.
+//! ```
+//!  use no_std_strings::{base32_encode, base32_decode, str24, str48};
+//!  let s: str24 = base32_encode(b"foobar", true).unwrap();
+//!  assert_eq!(s, "MZXW6YTBOI======");
+//!  let secret: str48 = base32_encode(b"12345678901234567890", false).unwrap();
+//!  assert_eq!(secret, "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+//!  let mut key = [0u8; 20];
+//!  let n = base32_decode(&secret, &mut key).unwrap();
+//!  assert_eq!(&key[..n], b"12345678901234567890");
+//!  assert_eq!(base32_decode("mzxw 6ytb oi", &mut key), Ok(6)); // case and spaces ignored
+//! ```
+
+use crate::{tstr, CapacityError};
+use core::fmt::Write;
+
+const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Error returned by [base32_decode]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Base32Error {
+    /// a character outside of the Base32 alphabet, or data after padding
+    InvalidChar(char),
+    /// the number of characters does not correspond to whole bytes
+    InvalidLength,
+    /// the output buffer is too small for the decoded bytes
+    BufferTooSmall,
+}
+
+impl core::fmt::Display for Base32Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Base32Error::InvalidChar(c) => write!(f, "invalid base32 character {:?}", c),
+            Base32Error::InvalidLength => f.write_str("invalid base32 length"),
+            Base32Error::BufferTooSmall => f.write_str("output buffer too small"),
+        }
+    }
+}
+
+/// encodes bytes as Base32 into a string of capacity M-1.  If `pad` is
+/// true, the output is padded with `=` to a multiple of 8 characters as
+/// required by RFC 4648; TOTP secrets are usually given unpadded.
+pub fn base32_encode<const M: usize>(data: &[u8], pad: bool) -> Result<tstr<M>, CapacityError> {
+    let mut out = tstr::<M>::new();
+    let (mut buf, mut bits, mut count) = (0u32, 0, 0);
+    for b in data {
+        buf = (buf << 8) | *b as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.write_char(ALPHABET[((buf >> bits) & 31) as usize] as char).map_err(|_| CapacityError)?;
+            count += 1;
+        }
+        buf &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        out.write_char(ALPHABET[((buf << (5 - bits)) & 31) as usize] as char).map_err(|_| CapacityError)?;
+        count += 1;
+    }
+    while pad && count % 8 != 0 {
+        out.write_char('=').map_err(|_| CapacityError)?;
+        count += 1;
+    }
+    Ok(out)
+} //base32_encode
+
+/// decodes Base32 text into `out`, returning the number of bytes written.
+/// Letters may be of either case, padding is optional and spaces are
+/// ignored.
+pub fn base32_decode(s: &str, out: &mut [u8]) -> Result<usize, Base32Error> {
+    let (mut buf, mut bits, mut chars, mut n) = (0u32, 0, 0, 0);
+    let mut padded = false;
+    for c in s.chars() {
+        if c == ' ' {
+            continue;
+        }
+        if c == '=' {
+            padded = true;
+            continue;
+        }
+        let v = match c.to_ascii_uppercase() {
+            u @ 'A'..='Z' if !padded => u as u32 - 'A' as u32,
+            d @ '2'..='7' if !padded => d as u32 - '2' as u32 + 26,
+            _ => return Err(Base32Error::InvalidChar(c)),
+        };
+        buf = (buf << 5) | v;
+        bits += 5;
+        chars += 1;
+        if bits >= 8 {
+            bits -= 8;
+            *out.get_mut(n).ok_or(Base32Error::BufferTooSmall)? = (buf >> bits) as u8;
+            n += 1;
+            buf &= (1 << bits) - 1;
+        }
+    }
+    if matches!(chars % 8, 1 | 3 | 6) {
+        return Err(Base32Error::InvalidLength);
+    }
+    Ok(n)
+} //base32_decode
@@ -0,0 +1,49 @@
+//! Collecting iterators of chars or string slices into fixed strings
+//! without silent truncation.
+
+use crate::CapacityError;
+use core::fmt::Write;
+
+/// Items that can be appended to a fixed string by [CollectFixed]:
+/// `char` and `&str`.
+pub trait StrPiece {
+    /// appends self to the writer, failing if capacity is exceeded
+    fn write_to<W: Write>(&self, w: &mut W) -> core::fmt::Result;
+}
+impl StrPiece for char {
+    fn write_to<W: Write>(&self, w: &mut W) -> core::fmt::Result {
+        w.write_char(*self)
+    }
+}
+impl StrPiece for &str {
+    fn write_to<W: Write>(&self, w: &mut W) -> core::fmt::Result {
+        w.write_str(self)
+    }
+}
+
+/// Extension trait for iterators over `char` or `&str`, collecting
+/// into any of the fixed string types.  Unlike truncating conversions,
+/// overflow is reported as an error.
+/// ```
+///  use no_std_strings::{str8, zstr, CollectFixed};
+///  let s: str8 = "abc".chars().rev().collect_fixed().unwrap();
+///  assert_eq!(s, "cba");
+///  let z = ["ab", "cd"].into_iter().collect_fixed::<zstr<8>>().unwrap();
+///  assert_eq!(z, "abcd");
+///  assert!("abcdefgh".chars().collect_fixed::<str8>().is_err());
+/// ```
+pub trait CollectFixed: Iterator + Sized
+where
+    Self::Item: StrPiece,
+{
+    /// collects all items into a fixed string of type T, returning
+    /// [CapacityError] if they do not fit.
+    fn collect_fixed<T: Default + Write>(self) -> Result<T, CapacityError> {
+        let mut s = T::default();
+        for item in self {
+            item.write_to(&mut s).map_err(|_| CapacityError)?;
+        }
+        Ok(s)
+    }
+}
+impl<I: Iterator> CollectFixed for I where I::Item: StrPiece {}
@@ -0,0 +1,49 @@
+//! Zero-copy concatenation for formatted output.
+
+use core::fmt::{Display, Formatter, Result};
+
+/// Displays a tuple, array or slice of string pieces one after another,
+/// so that they can be written to a [core::fmt::Write] sink without first
+/// being combined into a fixed string.  Each piece may be any
+/// `AsRef<str>`, including the fixed string types.
+/// ```
+///  use no_std_strings::{str8, zstr, str_format, str32, Concat};
+///  let (host, port) = (str8::from("broker"), zstr::<8>::from("1883"));
+///  let s = str_format!(str32, "{}", Concat((host, ":", port)));
+///  assert_eq!(s, "broker:1883");
+///  let path = ["a", "b", "c"];
+///  assert_eq!(str_format!(str32, "/{}", Concat(path)), "/abc");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Concat<T>(pub T);
+
+impl<S: AsRef<str>, const K: usize> Display for Concat<[S; K]> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.0.iter().try_for_each(|s| f.write_str(s.as_ref()))
+    }
+}
+
+impl<S: AsRef<str>> Display for Concat<&[S]> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.0.iter().try_for_each(|s| f.write_str(s.as_ref()))
+    }
+}
+
+macro_rules! tuple_concat {
+    ($($t: ident $i: tt),+) => {
+        impl<$($t: AsRef<str>),+> Display for Concat<($($t,)+)> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+                $(f.write_str(self.0.$i.as_ref())?;)+
+                Ok(())
+            }
+        }
+    };
+}
+tuple_concat!(A 0);
+tuple_concat!(A 0, B 1);
+tuple_concat!(A 0, B 1, C 2);
+tuple_concat!(A 0, B 1, C 2, D 3);
+tuple_concat!(A 0, B 1, C 2, D 3, E 4);
+tuple_concat!(A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_concat!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple_concat!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
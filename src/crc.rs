@@ -0,0 +1,73 @@
+//! Checksums over the contents of strings, enabled by `--features crc`.
+//! Both are computed bitwise, without lookup tables, over the bytes of
+//! the string (excluding the terminating zero of a zstr).
+
+use crate::{tstr, zstr};
+
+// CRC-32/ISO-HDLC, as used by Ethernet, zip and png
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for b in bytes {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+// CRC-16/CCITT-FALSE: polynomial 0x1021, initial value 0xFFFF
+pub(crate) fn crc16_ccitt(bytes: &[u8]) -> u16 {
+    let mut crc = 0xFFFFu16;
+    for b in bytes {
+        crc ^= (*b as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+impl<const N: usize> zstr<N> {
+    /// CRC-32 (the variant used by Ethernet and zip) of the string,
+    /// excluding the terminating zero.  Requires `--features crc`.
+    /// ```
+    ///  use no_std_strings::zstr;
+    ///  assert_eq!(zstr::<16>::from("123456789").crc32(), 0xCBF43926);
+    /// ```
+    pub fn crc32(&self) -> u32 {
+        crc32(self.to_str().as_bytes())
+    }
+
+    /// CRC-16/CCITT-FALSE (polynomial 0x1021, initial value 0xFFFF) of the
+    /// string, excluding the terminating zero.  Requires `--features crc`.
+    /// ```
+    ///  use no_std_strings::zstr;
+    ///  assert_eq!(zstr::<16>::from("123456789").crc16_ccitt(), 0x29B1);
+    /// ```
+    pub fn crc16_ccitt(&self) -> u16 {
+        crc16_ccitt(self.to_str().as_bytes())
+    }
+}
+
+impl<const N: usize> tstr<N> {
+    /// CRC-32 (the variant used by Ethernet and zip) of the string.
+    /// Requires `--features crc`.
+    /// ```
+    ///  use no_std_strings::str16;
+    ///  assert_eq!(str16::from("123456789").crc32(), 0xCBF43926);
+    /// ```
+    pub fn crc32(&self) -> u32 {
+        crc32(self.as_bytes())
+    }
+
+    /// CRC-16/CCITT-FALSE (polynomial 0x1021, initial value 0xFFFF) of the
+    /// string.  Requires `--features crc`.
+    /// ```
+    ///  use no_std_strings::str16;
+    ///  assert_eq!(str16::from("123456789").crc16_ccitt(), 0x29B1);
+    /// ```
+    pub fn crc16_ccitt(&self) -> u16 {
+        crc16_ccitt(self.as_bytes())
+    }
+}
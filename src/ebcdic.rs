@@ -0,0 +1,181 @@
+//! EBCDIC conversion for exchanging fixed-width records with mainframe
+//! systems, enabled by `--features ebcdic`.  The supported code pages
+//! are listed in [CodePage].  Each EBCDIC byte corresponds to a single
+//! Latin-1 character (or the euro sign for code page 1140), so only
+//! strings made of such characters can be converted to EBCDIC.
+//! ```
+//!  use no_std_strings::{str16, zstr, CodePage, EbcdicError};
+//!  let s = str16::from("HELLO, 42");
+//!  let mut rec = [0x40u8; 12];  // space-padded field
+//!  let n = s.to_ebcdic_into(&mut rec, CodePage::Cp037).unwrap();
+//!  assert_eq!(&rec[..n], &[0xC8, 0xC5, 0xD3, 0xD3, 0xD6, 0x6B, 0x40, 0xF4, 0xF2]);
+//!  let back = zstr::<16>::from_ebcdic(&rec[..n], CodePage::Cp037).unwrap();
+//!  assert_eq!(back, "HELLO, 42");
+//!  assert_eq!(str16::from("λ").to_ebcdic_into(&mut rec, CodePage::Cp500),
+//!             Err(EbcdicError::Unmappable('λ')));
+//! ```
+
+use crate::{tstr, zstr};
+use core::fmt::Write;
+
+/// EBCDIC code pages supported by the conversion functions
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CodePage {
+    /// IBM 037, US/Canada
+    Cp037,
+    /// IBM 500, International
+    Cp500,
+    /// IBM 1140, same as 037 but with the euro sign in place of `¤`
+    Cp1140,
+}
+
+/// Error returned by the EBCDIC conversion functions
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EbcdicError {
+    /// the character has no representation in the code page, or cannot
+    /// be stored in the target string
+    Unmappable(char),
+    /// the output buffer is too small
+    BufferTooSmall,
+    /// the decoded string exceeds the capacity of the target string
+    ExceedsCapacity,
+}
+
+impl core::fmt::Display for EbcdicError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            EbcdicError::Unmappable(c) => write!(f, "character {:?} cannot be converted", c),
+            EbcdicError::BufferTooSmall => f.write_str("output buffer too small"),
+            EbcdicError::ExceedsCapacity => f.write_str("string exceeds capacity"),
+        }
+    }
+}
+
+// EBCDIC byte to Latin-1 code point
+const CP037: [u8; 256] = [
+    0x00, 0x01, 0x02, 0x03, 0x9C, 0x09, 0x86, 0x7F, 0x97, 0x8D, 0x8E, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+    0x10, 0x11, 0x12, 0x13, 0x9D, 0x85, 0x08, 0x87, 0x18, 0x19, 0x92, 0x8F, 0x1C, 0x1D, 0x1E, 0x1F,
+    0x80, 0x81, 0x82, 0x83, 0x84, 0x0A, 0x17, 0x1B, 0x88, 0x89, 0x8A, 0x8B, 0x8C, 0x05, 0x06, 0x07,
+    0x90, 0x91, 0x16, 0x93, 0x94, 0x95, 0x96, 0x04, 0x98, 0x99, 0x9A, 0x9B, 0x14, 0x15, 0x9E, 0x1A,
+    0x20, 0xA0, 0xE2, 0xE4, 0xE0, 0xE1, 0xE3, 0xE5, 0xE7, 0xF1, 0xA2, 0x2E, 0x3C, 0x28, 0x2B, 0x7C,
+    0x26, 0xE9, 0xEA, 0xEB, 0xE8, 0xED, 0xEE, 0xEF, 0xEC, 0xDF, 0x21, 0x24, 0x2A, 0x29, 0x3B, 0xAC,
+    0x2D, 0x2F, 0xC2, 0xC4, 0xC0, 0xC1, 0xC3, 0xC5, 0xC7, 0xD1, 0xA6, 0x2C, 0x25, 0x5F, 0x3E, 0x3F,
+    0xF8, 0xC9, 0xCA, 0xCB, 0xC8, 0xCD, 0xCE, 0xCF, 0xCC, 0x60, 0x3A, 0x23, 0x40, 0x27, 0x3D, 0x22,
+    0xD8, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0xAB, 0xBB, 0xF0, 0xFD, 0xFE, 0xB1,
+    0xB0, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0xAA, 0xBA, 0xE6, 0xB8, 0xC6, 0xA4,
+    0xB5, 0x7E, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0xA1, 0xBF, 0xD0, 0xDD, 0xDE, 0xAE,
+    0x5E, 0xA3, 0xA5, 0xB7, 0xA9, 0xA7, 0xB6, 0xBC, 0xBD, 0xBE, 0x5B, 0x5D, 0xAF, 0xA8, 0xB4, 0xD7,
+    0x7B, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0xAD, 0xF4, 0xF6, 0xF2, 0xF3, 0xF5,
+    0x7D, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F, 0x50, 0x51, 0x52, 0xB9, 0xFB, 0xFC, 0xF9, 0xFA, 0xFF,
+    0x5C, 0xF7, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5A, 0xB2, 0xD4, 0xD6, 0xD2, 0xD3, 0xD5,
+    0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0xB3, 0xDB, 0xDC, 0xD9, 0xDA, 0x9F,
+];
+const CP500: [u8; 256] = [
+    0x00, 0x01, 0x02, 0x03, 0x9C, 0x09, 0x86, 0x7F, 0x97, 0x8D, 0x8E, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+    0x10, 0x11, 0x12, 0x13, 0x9D, 0x85, 0x08, 0x87, 0x18, 0x19, 0x92, 0x8F, 0x1C, 0x1D, 0x1E, 0x1F,
+    0x80, 0x81, 0x82, 0x83, 0x84, 0x0A, 0x17, 0x1B, 0x88, 0x89, 0x8A, 0x8B, 0x8C, 0x05, 0x06, 0x07,
+    0x90, 0x91, 0x16, 0x93, 0x94, 0x95, 0x96, 0x04, 0x98, 0x99, 0x9A, 0x9B, 0x14, 0x15, 0x9E, 0x1A,
+    0x20, 0xA0, 0xE2, 0xE4, 0xE0, 0xE1, 0xE3, 0xE5, 0xE7, 0xF1, 0x5B, 0x2E, 0x3C, 0x28, 0x2B, 0x21,
+    0x26, 0xE9, 0xEA, 0xEB, 0xE8, 0xED, 0xEE, 0xEF, 0xEC, 0xDF, 0x5D, 0x24, 0x2A, 0x29, 0x3B, 0x5E,
+    0x2D, 0x2F, 0xC2, 0xC4, 0xC0, 0xC1, 0xC3, 0xC5, 0xC7, 0xD1, 0xA6, 0x2C, 0x25, 0x5F, 0x3E, 0x3F,
+    0xF8, 0xC9, 0xCA, 0xCB, 0xC8, 0xCD, 0xCE, 0xCF, 0xCC, 0x60, 0x3A, 0x23, 0x40, 0x27, 0x3D, 0x22,
+    0xD8, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0xAB, 0xBB, 0xF0, 0xFD, 0xFE, 0xB1,
+    0xB0, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0xAA, 0xBA, 0xE6, 0xB8, 0xC6, 0xA4,
+    0xB5, 0x7E, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0xA1, 0xBF, 0xD0, 0xDD, 0xDE, 0xAE,
+    0xA2, 0xA3, 0xA5, 0xB7, 0xA9, 0xA7, 0xB6, 0xBC, 0xBD, 0xBE, 0xAC, 0x7C, 0xAF, 0xA8, 0xB4, 0xD7,
+    0x7B, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0xAD, 0xF4, 0xF6, 0xF2, 0xF3, 0xF5,
+    0x7D, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F, 0x50, 0x51, 0x52, 0xB9, 0xFB, 0xFC, 0xF9, 0xFA, 0xFF,
+    0x5C, 0xF7, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5A, 0xB2, 0xD4, 0xD6, 0xD2, 0xD3, 0xD5,
+    0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0xB3, 0xDB, 0xDC, 0xD9, 0xDA, 0x9F,
+];
+
+const fn invert(table: &[u8; 256]) -> [u8; 256] {
+    let mut inv = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        inv[table[i] as usize] = i as u8;
+        i += 1;
+    }
+    inv
+}
+const CP037_INV: [u8; 256] = invert(&CP037);
+const CP500_INV: [u8; 256] = invert(&CP500);
+
+const EURO_1140: u8 = 0x9F;
+
+impl CodePage {
+    /// converts one EBCDIC byte to a char
+    pub fn decode(self, b: u8) -> char {
+        match self {
+            CodePage::Cp037 => CP037[b as usize] as char,
+            CodePage::Cp500 => CP500[b as usize] as char,
+            CodePage::Cp1140 if b == EURO_1140 => '€',
+            CodePage::Cp1140 => CP037[b as usize] as char,
+        }
+    }
+
+    /// converts a char to its EBCDIC byte, if it has one
+    pub fn encode(self, c: char) -> Option<u8> {
+        match (self, c) {
+            (CodePage::Cp1140, '€') => Some(EURO_1140),
+            (CodePage::Cp1140, '\u{a4}') => None,
+            (_, c) if (c as u32) > 0xFF => None,
+            (CodePage::Cp500, c) => Some(CP500_INV[c as usize]),
+            (_, c) => Some(CP037_INV[c as usize]),
+        }
+    }
+} //impl CodePage
+
+fn encode_into(s: &str, out: &mut [u8], cp: CodePage) -> Result<usize, EbcdicError> {
+    let mut n = 0;
+    for c in s.chars() {
+        let b = cp.encode(c).ok_or(EbcdicError::Unmappable(c))?;
+        *out.get_mut(n).ok_or(EbcdicError::BufferTooSmall)? = b;
+        n += 1;
+    }
+    Ok(n)
+}
+
+impl<const N: usize> zstr<N> {
+    /// converts the string to EBCDIC in the given code page, writing into
+    /// `out`.  Returns the number of bytes written; the rest of `out` is
+    /// left unchanged.  Requires `--features ebcdic`.
+    pub fn to_ebcdic_into(&self, out: &mut [u8], cp: CodePage) -> Result<usize, EbcdicError> {
+        encode_into(self.to_str(), out, cp)
+    }
+
+    /// constructs a zstr from EBCDIC bytes in the given code page.  Since
+    /// zstr is zero-terminated, an EBCDIC zero byte is an error.
+    /// Requires `--features ebcdic`.
+    pub fn from_ebcdic(bytes: &[u8], cp: CodePage) -> Result<zstr<N>, EbcdicError> {
+        let mut s = zstr::<N>::new();
+        for b in bytes {
+            let c = cp.decode(*b);
+            if c == '\0' {
+                return Err(EbcdicError::Unmappable(c));
+            }
+            s.write_char(c).map_err(|_| EbcdicError::ExceedsCapacity)?;
+        }
+        Ok(s)
+    }
+} //impl zstr
+
+impl<const N: usize> tstr<N> {
+    /// converts the string to EBCDIC in the given code page, writing into
+    /// `out`.  Returns the number of bytes written; the rest of `out` is
+    /// left unchanged.  Requires `--features ebcdic`.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_ebcdic_into(&self, out: &mut [u8], cp: CodePage) -> Result<usize, EbcdicError> {
+        encode_into(self.to_str(), out, cp)
+    }
+
+    /// constructs a string from EBCDIC bytes in the given code page.
+    /// Requires `--features ebcdic`.
+    pub fn from_ebcdic(bytes: &[u8], cp: CodePage) -> Result<tstr<N>, EbcdicError> {
+        let mut s = tstr::<N>::new();
+        for b in bytes {
+            s.write_char(cp.decode(*b)).map_err(|_| EbcdicError::ExceedsCapacity)?;
+        }
+        Ok(s)
+    }
+} //impl tstr
@@ -0,0 +1,141 @@
+//! Header names for small HTTP and CoAP implementations, compared
+//! without regard to ASCII case.
+
+use crate::{tstr, CapacityError};
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+/// Errors returned by [HeaderName::parse]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HeaderNameError {
+    /// the name is empty or contains a character not allowed in an
+    /// HTTP token, such as a space or `:`
+    Invalid,
+    /// the name does not fit in the fixed string
+    ExceedsCapacity,
+}
+
+impl core::fmt::Display for HeaderNameError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            HeaderNameError::Invalid => "invalid header name",
+            HeaderNameError::ExceedsCapacity => "header name exceeds capacity",
+        })
+    }
+}
+
+impl From<CapacityError> for HeaderNameError {
+    fn from(_: CapacityError) -> Self {
+        HeaderNameError::ExceedsCapacity
+    }
+}
+
+fn is_token_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+/// A header name stored in a tstr\<N\> as given, with Eq, Ord and Hash
+/// ignoring ASCII case, so that it can key maps of headers.
+/// ```
+///  use no_std_strings::HeaderName;
+///  let a = HeaderName::<32>::parse("content-TYPE").unwrap();
+///  let b = HeaderName::<32>::parse("Content-Type").unwrap();
+///  assert_eq!(a, b);
+///  assert!(a == "CONTENT-type");
+///  assert_eq!(a.as_str(), "content-TYPE");
+///  assert_eq!(a.canonical(), "Content-Type");
+///  assert!(HeaderName::<32>::parse("Bad Name").is_err());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct HeaderName<const N: usize>(tstr<N>);
+
+impl<const N: usize> HeaderName<N> {
+    /// validates that s is an HTTP token and copies it, without truncation
+    pub fn parse(s: &str) -> Result<Self, HeaderNameError> {
+        if s.is_empty() || !s.bytes().all(is_token_byte) {
+            return Err(HeaderNameError::Invalid);
+        }
+        Ok(HeaderName(tstr::try_make(s).map_err(|_| HeaderNameError::ExceedsCapacity)?))
+    }
+
+    /// returns the name as given
+    pub fn as_str(&self) -> &str {
+        self.0.to_str()
+    }
+
+    /// returns the name in canonical form: the first letter and each
+    /// letter following a `-` in upper case, all others in lower case
+    pub fn canonical(&self) -> tstr<N> {
+        let mut c = self.0;
+        c.make_ascii_lowercase();
+        let mut up = true;
+        for i in 0..c.len() {
+            let b = c.get_byte(i).unwrap_or(0);
+            if up && b.is_ascii_lowercase() {
+                c.set(i, b.to_ascii_uppercase() as char);
+            }
+            up = b == b'-';
+        }
+        c
+    } //canonical
+
+    /// converts the stored name to canonical form, see [HeaderName::canonical]
+    pub fn canonicalize(&mut self) {
+        self.0 = self.canonical();
+    }
+
+    /// returns the wrapped string
+    pub fn into_inner(self) -> tstr<N> {
+        self.0
+    }
+
+    fn lower(&self) -> impl Iterator<Item = u8> + '_ {
+        self.0.as_bytes().iter().map(u8::to_ascii_lowercase)
+    }
+} //impl HeaderName
+
+impl<const N: usize> core::ops::Deref for HeaderName<N> {
+    type Target = str;
+    fn deref(&self) -> &str {
+        self.0.to_str()
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<HeaderName<M>> for HeaderName<N> {
+    fn eq(&self, other: &HeaderName<M>) -> bool {
+        self.0.to_str().eq_ignore_ascii_case(other.0.to_str())
+    }
+}
+impl<const N: usize> Eq for HeaderName<N> {}
+
+impl<const N: usize> PartialEq<&str> for HeaderName<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0.to_str().eq_ignore_ascii_case(other)
+    }
+}
+
+impl<const N: usize, const M: usize> PartialOrd<HeaderName<M>> for HeaderName<N> {
+    fn partial_cmp(&self, other: &HeaderName<M>) -> Option<Ordering> {
+        Some(self.lower().cmp(other.lower()))
+    }
+}
+impl<const N: usize> Ord for HeaderName<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.lower().cmp(other.lower())
+    }
+}
+
+impl<const N: usize> Hash for HeaderName<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for b in self.lower() {
+            state.write_u8(b);
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl<const N: usize> core::fmt::Display for HeaderName<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad(self.0.to_str())
+    }
+}
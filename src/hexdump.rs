@@ -0,0 +1,76 @@
+//! Hex dumps of byte buffers into fixed strings, for debugging over a
+//! serial console without alloc.
+
+use crate::tstr;
+use core::fmt::Write;
+
+const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+// writes the bytes as hex digits, with a space before each group of
+// `group` bytes except the first (no spaces if group is 0).  Each byte
+// is written as a single piece so truncation never splits it.
+fn write_hex<W: Write>(w: &mut W, bytes: &[u8], group: usize) -> core::fmt::Result {
+    for (i, b) in bytes.iter().enumerate() {
+        let piece = [b' ', HEX[(b >> 4) as usize], HEX[(b & 15) as usize]];
+        let sep = (i > 0 && group > 0 && i % group == 0) as usize;
+        w.write_str(core::str::from_utf8(&piece[1 - sep..]).unwrap())?;
+    }
+    Ok(())
+} //write_hex
+
+/// formats bytes as upper case hex, with groups of `group` bytes
+/// separated by a space; 0 means no separators.  Output that exceeds
+/// the capacity of tstr\<M\> is truncated after the last whole byte.
+/// ```
+///  use no_std_strings::{hexdump_into, str16};
+///  let b = [0xde, 0xad, 0xbe, 0xef];
+///  let s: str16 = hexdump_into(&b, 1);
+///  assert_eq!(s, "DE AD BE EF");
+///  assert_eq!(hexdump_into::<16>(&b, 2), "DEAD BEEF");
+///  assert_eq!(hexdump_into::<8>(&b, 0), "DEADBE");
+/// ```
+pub fn hexdump_into<const M: usize>(bytes: &[u8], group: usize) -> tstr<M> {
+    let mut t = tstr::<M>::new();
+    let _ = write_hex(&mut t, bytes, group);
+    t
+}
+
+/// formats bytes as a multi-line dump of `width` bytes per line
+/// (16 if width is 0).  Each line holds the hex offset, the bytes, and
+/// their printable ASCII characters with '.' for all others, and ends
+/// with a newline.  Only whole lines are kept if the capacity of
+/// tstr\<M\> is exceeded.
+/// ```
+///  use no_std_strings::{hexdump_lines_into, str256};
+///  let s: str256 = hexdump_lines_into(b"OK\r\n+CSQ: 23,99", 8);
+///  assert_eq!(s, "0000: 4F 4B 0D 0A 2B 43 53 51  OK..+CSQ\n\
+///                 0008: 3A 20 32 33 2C 39 39     : 23,99\n");
+///  assert_eq!(hexdump_lines_into::<64>(&[0; 32], 0).len(), 0);
+/// ```
+pub fn hexdump_lines_into<const M: usize>(bytes: &[u8], width: usize) -> tstr<M> {
+    let width = if width == 0 { 16 } else { width };
+    let mut t = tstr::<M>::new();
+    for (n, line) in bytes.chunks(width).enumerate() {
+        let start = t.len();
+        let r = write!(t, "{:04X}: ", n * width)
+            .and_then(|_| write_hex(&mut t, line, 1))
+            .and_then(|_| {
+                for _ in line.len()..width {
+                    t.write_str("   ")?;
+                }
+                t.write_str("  ")
+            })
+            .and_then(|_| {
+                for &b in line {
+                    let c = if b.is_ascii_graphic() || b == b' ' { b } else { b'.' };
+                    t.write_char(c as char)?;
+                }
+                t.write_char('\n')
+            });
+        if r.is_err() {
+            t.truncate_bytes(start);
+            break;
+        }
+    } //for each line
+    t
+} //hexdump_lines_into
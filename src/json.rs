@@ -0,0 +1,213 @@
+//! Extraction of single fields from small JSON documents, such as device
+//! shadows and configuration blobs, without building a DOM.  Keys are
+//! looked up by path: `"state.reported.fw"` descends through nested
+//! objects.  Only members of objects are found, never keys appearing in
+//! string values or arrays.  The documents are assumed to be well
+//! formed; malformed input yields None rather than an error.
+
+use crate::tstr;
+use core::fmt::Write;
+
+/// Error returned by [json_get_string]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JsonError {
+    /// the key is absent or its value is not a string
+    Missing,
+    /// the string contains an invalid escape sequence
+    InvalidEscape,
+    /// the unescaped string exceeds capacity
+    ExceedsCapacity,
+}
+
+impl core::fmt::Display for JsonError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            JsonError::Missing => "JSON string field not found",
+            JsonError::InvalidEscape => "invalid escape in JSON string",
+            JsonError::ExceedsCapacity => "JSON string exceeds capacity",
+        })
+    }
+}
+
+fn skip_ws(b: &[u8], mut i: usize) -> usize {
+    while i < b.len() && b[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+// b[i] is the opening quote; returns the position after the closing one
+fn skip_string(b: &[u8], mut i: usize) -> Option<usize> {
+    i += 1;
+    while i < b.len() {
+        match b[i] {
+            b'"' => return Some(i + 1),
+            b'\\' => i += 2,
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+// returns the position after the value starting at b[i]
+fn skip_value(b: &[u8], mut i: usize) -> Option<usize> {
+    match b.get(i)? {
+        b'"' => skip_string(b, i),
+        b'{' | b'[' => {
+            let mut depth = 0;
+            while i < b.len() {
+                match b[i] {
+                    b'"' => {
+                        i = skip_string(b, i)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            None
+        }
+        _ => {
+            // number, true, false or null
+            let start = i;
+            while i < b.len() && !matches!(b[i], b',' | b'}' | b']') && !b[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            if i > start { Some(i) } else { None }
+        }
+    }
+} //skip_value
+
+// returns the text of the value of the member key of the object obj
+fn member<'a>(obj: &'a str, key: &str) -> Option<&'a str> {
+    let b = obj.as_bytes();
+    let mut i = skip_ws(b, 0);
+    if b.get(i) != Some(&b'{') {
+        return None;
+    }
+    loop {
+        i = skip_ws(b, i + 1);
+        if b.get(i) != Some(&b'"') {
+            return None; // empty object or malformed
+        }
+        let kend = skip_string(b, i)?;
+        let k = &obj[i + 1..kend - 1];
+        i = skip_ws(b, kend);
+        if b.get(i) != Some(&b':') {
+            return None;
+        }
+        let vstart = skip_ws(b, i + 1);
+        let vend = skip_value(b, vstart)?;
+        if k == key {
+            return Some(&obj[vstart..vend]);
+        }
+        i = skip_ws(b, vend);
+        if b.get(i) != Some(&b',') {
+            return None;
+        }
+    }
+} //member
+
+// returns the text of the value at the dotted path
+fn lookup<'a>(doc: &'a str, path: &str) -> Option<&'a str> {
+    path.split('.').try_fold(doc, member)
+}
+
+/// returns the contents of the string value at the path, without the
+/// quotes and with escape sequences left as they are.  Use
+/// [json_get_string] to unescape.
+/// ```
+///  use no_std_strings::{json_get_str, json_get_int, json_get_bool};
+///  let doc = r#"{"id": "dev-7", "note": "key \"id\": x",
+///               "state": {"reported": {"fw": "1.2.0", "rssi": -61, "ota": true}}}"#;
+///  assert_eq!(json_get_str(doc, "id"), Some("dev-7"));
+///  assert_eq!(json_get_str(doc, "state.reported.fw"), Some("1.2.0"));
+///  assert_eq!(json_get_int(doc, "state.reported.rssi"), Some(-61));
+///  assert_eq!(json_get_bool(doc, "state.reported.ota"), Some(true));
+///  assert_eq!(json_get_str(doc, "fw"), None);
+///  assert_eq!(json_get_str(doc, "state.reported.rssi"), None);
+/// ```
+pub fn json_get_str<'a>(doc: &'a str, path: &str) -> Option<&'a str> {
+    let v = lookup(doc, path)?;
+    v.strip_prefix('"')?.strip_suffix('"')
+}
+
+/// returns the integer value at the path.  Numbers with a fraction or
+/// exponent, or out of the range of i64, yield None.
+pub fn json_get_int(doc: &str, path: &str) -> Option<i64> {
+    lookup(doc, path)?.parse().ok()
+}
+
+/// returns the boolean value at the path
+pub fn json_get_bool(doc: &str, path: &str) -> Option<bool> {
+    match lookup(doc, path)? {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// copies the string value at the path into a tstr\<M\>, resolving
+/// escape sequences including `\uXXXX` and surrogate pairs
+/// ```
+///  use no_std_strings::{json_get_string, str32, JsonError};
+///  let doc = r#"{"ssid": "café \"5G\"", "pw": "\ud83d\ude00", "bad": "\x"}"#;
+///  let ssid: str32 = json_get_string(doc, "ssid").unwrap();
+///  assert_eq!(ssid, "café \"5G\"");
+///  assert_eq!(json_get_string::<8>(doc, "pw").unwrap(), "😀");
+///  assert_eq!(json_get_string::<8>(doc, "bad"), Err(JsonError::InvalidEscape));
+///  assert_eq!(json_get_string::<8>(doc, "ssid"), Err(JsonError::ExceedsCapacity));
+///  assert_eq!(json_get_string::<8>(doc, "key"), Err(JsonError::Missing));
+/// ```
+pub fn json_get_string<const M: usize>(doc: &str, path: &str) -> Result<tstr<M>, JsonError> {
+    let raw = json_get_str(doc, path).ok_or(JsonError::Missing)?;
+    let mut t = tstr::<M>::new();
+    let mut rest = raw;
+    while let Some(i) = rest.find('\\') {
+        t.write_str(&rest[..i]).map_err(|_| JsonError::ExceedsCapacity)?;
+        let (c, used) = unescape(&rest[i + 1..]).ok_or(JsonError::InvalidEscape)?;
+        t.write_char(c).map_err(|_| JsonError::ExceedsCapacity)?;
+        rest = &rest[i + 1 + used..];
+    }
+    t.write_str(rest).map_err(|_| JsonError::ExceedsCapacity)?;
+    Ok(t)
+} //json_get_string
+
+// decodes the escape sequence following a backslash, returning the
+// character and the number of bytes used
+fn unescape(s: &str) -> Option<(char, usize)> {
+    let hex = |s: &str| -> Option<u32> {
+        let h = s.get(..4)?;
+        if h.bytes().all(|b| b.is_ascii_hexdigit()) { u32::from_str_radix(h, 16).ok() } else { None }
+    };
+    let c = match s.as_bytes().first()? {
+        b'"' => '"',
+        b'\\' => '\\',
+        b'/' => '/',
+        b'b' => '\u{8}',
+        b'f' => '\u{c}',
+        b'n' => '\n',
+        b'r' => '\r',
+        b't' => '\t',
+        b'u' => {
+            let hi = hex(&s[1..])?;
+            if !(0xd800..0xdc00).contains(&hi) {
+                return char::from_u32(hi).map(|c| (c, 5));
+            }
+            let lo = hex(s.get(5..)?.strip_prefix("\\u")?)?;
+            if !(0xdc00..0xe000).contains(&lo) {
+                return None;
+            }
+            return char::from_u32(0x10000 + ((hi - 0xd800) << 10) + (lo - 0xdc00)).map(|c| (c, 11));
+        }
+        _ => return None,
+    };
+    Some((c, 1))
+} //unescape
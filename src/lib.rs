@@ -13,11 +13,35 @@
 //! be referenced using the aliases.  With few exceptions the tstr type
 //! implement the same functions and traits as [zstr].
 //!  
+//! Both types dereference to `str`, so iterators such as `chars`, `bytes`
+//! and `split` are those of `core::str` and are double-ended: suffix
+//! scans need no special support.
+//! ```
+//!  use no_std_strings::{str32, zstr};
+//!  let f = zstr::<32>::from("firmware.v2.bin");
+//!  assert_eq!(f.rsplit('.').next(), Some("bin"));
+//!  let s = str32::from("sensor42");
+//!  let digits = s.bytes().rev().take_while(|b| b.is_ascii_digit()).count();
+//!  assert_eq!(&s[s.len()-digits..], "42");
+//!  assert_eq!(s.chars().next_back(), Some('2'));
+//! ```
+//!
 //! Compared to their counterparts in
 //! [fixedstr](https://docs.rs/fixedstr/latest/fixedstr/), some functions
 //! were omitted to accommodate the `#![no_std]` requirement.
 //!
-//! Optional serde serialization support is enabled by `--features serde`.
+//! Optional serde serialization support is enabled by `--features serde`;
+//! binary formats use a compact byte representation, which
+//! `--features serde-array` changes to a fixed-size array.
+//! Strategies for property testing with
+//! [proptest](https://docs.rs/proptest/latest/proptest/) are enabled by
+//! `--features proptest`.  Conversion to and from EBCDIC code pages is
+//! enabled by `--features ebcdic`, and Unicode case-insensitive
+//! comparison by `--features unicase`.  CRC checksums over string
+//! contents are enabled by `--features crc`.  Comparisons with `String`
+//! and `Cow<str>` are enabled by `--features alloc`.  [AtomicStr8] is
+//! available on targets with native 64-bit atomics, and on others, such
+//! as thumbv6m, with `--features portable-atomic`.
 
 
 #![no_std]
@@ -31,11 +55,73 @@
 #![allow(unused_imports)]
 #![allow(dead_code)]
 
+#[cfg(feature="alloc")]
+extern crate alloc;
+
 mod zero_terminated;
 pub use zero_terminated::*;
 
 mod tiny_internal;
 use tiny_internal::*;
+pub use tiny_internal::concat_all;
+
+mod collect;
+pub use collect::*;
+
+mod aligned;
+pub use aligned::*;
+
+mod phonetic;
+mod numparse;
+mod ansi;
+
+mod base32;
+pub use base32::*;
+
+mod concat;
+pub use concat::*;
+
+mod arena;
+pub use arena::*;
+
+mod template;
+pub use template::*;
+
+mod args;
+pub use args::*;
+
+mod hexdump;
+pub use hexdump::*;
+
+mod header;
+pub use header::*;
+
+mod json;
+pub use json::*;
+
+pub mod style;
+pub mod url_lite;
+pub mod mqtt;
+pub mod at;
+pub mod nmea_lite;
+
+#[cfg(feature="ebcdic")]
+mod ebcdic;
+#[cfg(feature="ebcdic")]
+pub use ebcdic::*;
+
+#[cfg(feature="unicase")]
+mod unicase;
+#[cfg(feature="unicase")]
+pub use unicase::*;
+
+#[cfg(feature="crc")]
+mod crc;
+
+#[cfg(any(feature="portable-atomic", target_has_atomic="64"))]
+mod atomic;
+#[cfg(any(feature="portable-atomic", target_has_atomic="64"))]
+pub use atomic::*;
 
 /// Types for small strings that use a more efficient representation
 /// underneath.  A str8 can hold a string of up to 7 bytes (7 ascii chars).
@@ -95,6 +181,17 @@ pub type str192 = tstr<192>;
 
 
 
+/// Error returned when a string does not fit in the capacity of the
+/// fixed string type it is being stored in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CapacityError;
+
+impl core::fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("capacity of fixed string exceeded")
+    }
+}
+
 #[macro_export]
 /// creates a formated string of given type (by implementing [core::fmt::Write]):
 /// ```ignore
@@ -126,37 +223,329 @@ macro_rules! try_format {
   };
 }
 
+#[macro_export]
+/// version of [str_format]! that marks truncated output: if the
+/// formatted string exceeds capacity, as much as fits is kept and its
+/// final character(s) are overwritten with '…', so that clipped log
+/// lines are visibly marked.  The ellipsis takes 3 bytes, so types of
+/// capacity less than 3 are truncated without the mark.
+/// ```
+///  use no_std_strings::{str_format_ellipsis, str16, zstr};
+///  let s = str_format_ellipsis!(str16, "temp={} humidity={}", 21, 40);
+///  assert_eq!(s, "temp=21 humi…");
+///  assert_eq!(s.len(), 15);
+///  let z = str_format_ellipsis!(zstr<16>, "ok {}", 1);
+///  assert_eq!(z, "ok 1");
+/// ```
+macro_rules! str_format_ellipsis {
+  ($ty_size:ty, $($args:tt)*) => {
+     {let mut fstr0 = <$ty_size>::new();
+     let mut cut = false;
+     let _ = core::fmt::write(&mut $crate::FnWriter(|s: &str| {
+         if !cut { cut = !fstr0.push(s).is_empty(); }
+     }), format_args!($($args)*));
+     let cap = fstr0.capacity();
+     if cut && cap >= 3 {
+         let b = fstr0.char_indices().rev().map(|(i, _)| i).find(|i| i + 3 <= cap).unwrap_or(0);
+         fstr0.truncate_bytes(b);
+         fstr0.push("\u{2026}");
+     }
+     fstr0}
+  };
+}
+
+#[macro_export]
+/// match-like dispatch of a [str8] token against string literals.  Each
+/// literal is converted to a word at compile time with
+/// `str8::const_make` and `str8::to_u64`, so every arm is a
+/// single integer comparison instead of a string comparison.  Literals
+/// longer than 7 bytes are a compile-time error.  Arms are separated by
+/// commas and the final arm must be the wildcard `_`.
+/// ```
+///  use no_std_strings::{str_match, str8};
+///  fn dispatch(tok: str8) -> u8 {
+///    str_match!(tok, {
+///      "on" => 1,
+///      "off" | "stop" => 2,
+///      "reset" => { let code = 3; code },
+///      _ => 0,
+///    })
+///  }
+///  assert_eq!(dispatch(str8::from("off")), 2);
+///  assert_eq!(dispatch(str8::from("stop")), 2);
+///  assert_eq!(dispatch(str8::from("reset")), 3);
+///  assert_eq!(dispatch(str8::from("o")), 0);
+/// ```
+/// ```compile_fail
+///  use no_std_strings::{str_match, str8};
+///  let n = str_match!(str8::from("x"), { "too-long" => 1, _ => 0, });
+/// ```
+macro_rules! str_match {
+  ($tok:expr, { $($($lit:literal)|+ => $arm:expr,)* _ => $default:expr $(,)? }) => {
+     {let word: u64 = $tok.to_u64();
+     $(if $(word == { const W: u64 = $crate::str8::const_make($lit).to_u64(); W })||+ { $arm } else)*
+     { $default }}
+  };
+}
+
+#[doc(hidden)]
+/// adapter implementing [core::fmt::Write] by calling a closure on each
+/// piece of output, used by [str_format_ellipsis]
+pub struct FnWriter<F: FnMut(&str)>(pub F);
+
+impl<F: FnMut(&str)> core::fmt::Write for FnWriter<F> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        (self.0)(s);
+        Ok(())
+    }
+}
+
+
+#[cfg(feature="serde")]
+pub use serde_support::Truncating;
 
 #[cfg(feature="serde")]
+/// Human-readable formats such as JSON represent strings as strings.
+/// Binary formats (those whose serializer is not
+/// [human readable](serde::Serializer::is_human_readable)) use a compact
+/// byte representation: by default the bytes of the string, which formats
+/// such as bincode encode as length followed by bytes.  With
+/// `--features serde-array` they instead use a fixed array of N bytes
+/// mirroring the in-memory layout, so that every value of a type has the
+/// same encoded size: for `zstr<N>` the zero-terminated and zero-padded
+/// bytes, and for `tstr<N>` the length byte followed by the zero-padded
+/// bytes.
+/// ```
+///  use no_std_strings::{str8, zstr};
+///  let (z, t) = (zstr::<8>::from("abc"), str8::from("abc"));
+///  let (zb, tb) = (bincode::serialize(&z).unwrap(), bincode::serialize(&t).unwrap());
+///  #[cfg(not(feature = "serde-array"))]
+///  {
+///    // bincode writes a u64 length before the bytes
+///    assert_eq!(zb, [3, 0, 0, 0, 0, 0, 0, 0, b'a', b'b', b'c']);
+///    assert_eq!(tb, zb);
+///    let long = bincode::serialize(&zstr::<16>::from("abcdefgh")).unwrap();
+///    assert!(bincode::deserialize::<zstr<8>>(&long).is_err());
+///    assert!(bincode::deserialize::<str8>(&[1, 0, 0, 0, 0, 0, 0, 0, 0xff]).is_err());
+///    // formats that present bytes as a sequence go through visit_seq
+///    use serde::Deserialize;
+///    use serde::de::value::{Error, SeqDeserializer};
+///    let seq = SeqDeserializer::<_, Error>::new([b'o', b'k'].into_iter());
+///    assert_eq!(zstr::<8>::deserialize(seq).unwrap(), "ok");
+///    let seq = SeqDeserializer::<_, Error>::new([b'x'; 8].into_iter());
+///    assert!(str8::deserialize(seq).is_err());
+///  }
+///  #[cfg(feature = "serde-array")]
+///  {
+///    assert_eq!(zb, [b'a', b'b', b'c', 0, 0, 0, 0, 0]);
+///    assert_eq!(tb, [3, b'a', b'b', b'c', 0, 0, 0, 0]);
+///    assert!(bincode::deserialize::<str8>(&[8, b'a', b'b', b'c', 0, 0, 0, 0]).is_err());
+///    assert!(bincode::deserialize::<str8>(&[1, 0xff, 0, 0, 0, 0, 0, 0]).is_err());
+///    assert!(bincode::deserialize::<zstr<8>>(&[b'x'; 8]).is_err());
+///    assert!(bincode::deserialize::<zstr<8>>(&[0xff, 0, 0, 0, 0, 0, 0, 0]).is_err());
+///  }
+///  assert_eq!(bincode::deserialize::<zstr<8>>(&zb).unwrap(), z);
+///  assert_eq!(bincode::deserialize::<str8>(&tb).unwrap(), t);
+/// ```
 mod serde_support {
-    use serde::{Serialize, Deserialize, Serializer, Deserializer, de::Visitor};
+    use serde::{Serialize, Deserialize, Serializer, Deserializer, de::{Error, SeqAccess, Visitor}};
     use super::*;
     macro_rules! generate_impl {
-        ($ty: ident, $visitor: ident) => {
+        ($ty: ident, $visitor: ident, $prefix: expr) => {
             impl<const N: usize> Serialize for $ty<N> {
                 fn serialize<S: Serializer>(&self, serializer:S) -> Result<S::Ok, S::Error> {
-                    serializer.serialize_str(self.as_str())
+                    if serializer.is_human_readable() {
+                        return serializer.serialize_str(self.as_str());
+                    }
+                    #[cfg(not(feature="serde-array"))]
+                    return serializer.serialize_bytes(self.to_str().as_bytes());
+                    #[cfg(feature="serde-array")]
+                    {
+                        use serde::ser::SerializeTuple;
+                        let bytes = self.to_str().as_bytes();
+                        let mut t = serializer.serialize_tuple(N)?;
+                        if $prefix == 1 { t.serialize_element(&(bytes.len() as u8))?; }
+                        for b in bytes { t.serialize_element(b)?; }
+                        for _ in bytes.len() + $prefix..N { t.serialize_element(&0u8)?; }
+                        t.end()
+                    }
                 }
             }
             impl<'de, const N: usize> Deserialize<'de> for $ty<N> {
                 fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-                    deserializer.deserialize_str($visitor)
+                    deserialize_with(deserializer, N, $visitor::<N, false>)
                 }
             }
-            struct $visitor<const N: usize>;
-            impl<'de, const N: usize> Visitor<'de> for $visitor<N> {
+            impl<'de, const N: usize> Deserialize<'de> for Truncating<$ty<N>> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserialize_with(deserializer, N, $visitor::<N, true>).map(Truncating)
+                }
+            }
+            // TRUNC selects truncation instead of an error on long input
+            struct $visitor<const N: usize, const TRUNC: bool>;
+            impl<'de, const N: usize, const TRUNC: bool> Visitor<'de> for $visitor<N, TRUNC> {
                 type Value = $ty<N>;
                 fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                     f.write_str("a string")
                 }
-                fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<Self::Value, E> {
+                fn visit_str<E: Error>(self, s: &str) -> Result<Self::Value, E> {
+                    if TRUNC && s.len() > N - 1 {
+                        return self.visit_bytes(char_prefix(s.as_bytes(), N - 1));
+                    }
+                    $ty::try_make(s).map_err(|_| E::custom("string too long"))
+                }
+                fn visit_bytes<E: Error>(self, b: &[u8]) -> Result<Self::Value, E> {
+                    let b = if TRUNC { char_prefix(b, N - 1) } else { b };
+                    let s = core::str::from_utf8(b).map_err(|_| E::custom("invalid utf8"))?;
                     $ty::try_make(s).map_err(|_| E::custom("string too long"))
                 }
+                #[cfg(not(feature="serde-array"))]
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                    let mut buf = [0u8; N];
+                    let mut len = 0;
+                    while let Some(b) = seq.next_element::<u8>()? {
+                        if len + 1 >= N && !TRUNC { return Err(A::Error::custom("string too long")); }
+                        // when truncating, one byte past capacity is kept to find the cut
+                        if len < N { buf[len] = b; len += 1; }
+                    }
+                    self.visit_bytes(&buf[..len])
+                }
+                #[cfg(feature="serde-array")]
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                    let mut buf = [0u8; N];
+                    for (i, b) in buf.iter_mut().enumerate() {
+                        *b = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(i, &self))?;
+                    }
+                    let content = if $prefix == 1 {
+                        buf.get(1..buf[0] as usize + 1).ok_or_else(|| A::Error::custom("invalid length byte"))?
+                    } else {
+                        let end = buf.iter().position(|b| *b == 0).ok_or_else(|| A::Error::custom("missing terminating zero"))?;
+                        &buf[..end]
+                    };
+                    self.visit_bytes(content)
+                }
+            }
+        }
+    }
+    /// Wrapper whose Deserialize implementation truncates strings that
+    /// exceed capacity, at a character boundary, instead of failing.
+    /// Serialization is that of the wrapped string.  With
+    /// `--features serde-array` the size of the binary representation is
+    /// fixed by the type, so truncation only applies to human-readable
+    /// formats.
+    /// ```
+    ///  use no_std_strings::{str8, Truncating};
+    ///  use serde::Deserialize;
+    ///  use serde::de::{IntoDeserializer, value::{Error, StrDeserializer}};
+    ///  let d: StrDeserializer<Error> = "héllo world".into_deserializer();
+    ///  assert_eq!(Truncating::<str8>::deserialize(d).unwrap().0, "héllo ");
+    ///  let d: StrDeserializer<Error> = "héllo world".into_deserializer();
+    ///  assert!(str8::deserialize(d).is_err());
+    /// ```
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct Truncating<T>(pub T);
+
+    impl<T> core::ops::Deref for Truncating<T> {
+        type Target = T;
+        fn deref(&self) -> &T {
+            &self.0
+        }
+    }
+    impl<T: Serialize> Serialize for Truncating<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    // longest prefix of b with at most n bytes that does not end inside a
+    // utf8 character
+    fn char_prefix(b: &[u8], n: usize) -> &[u8] {
+        if b.len() <= n {
+            return b;
+        }
+        let mut i = n;
+        while i > 0 && (b[i] & 0xC0) == 0x80 {
+            i -= 1;
+        }
+        &b[..i]
+    }
+
+    // n is the size of the fixed array representation
+    fn deserialize_with<'de, D: Deserializer<'de>, V: Visitor<'de>>(deserializer: D, n: usize, visitor: V) -> Result<V::Value, D::Error> {
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_str(visitor);
+        }
+        #[cfg(not(feature="serde-array"))]
+        return deserializer.deserialize_bytes(visitor);
+        #[cfg(feature="serde-array")]
+        return deserializer.deserialize_tuple(n, visitor);
+    }
+
+    generate_impl!(zstr, ZstrVisitor, 0);
+    generate_impl!(tstr, TstrVisitor, 1);
+}
+
+#[cfg(feature="proptest")]
+/// Strategies for generating fixed strings in property tests.  The
+/// types also implement [proptest::arbitrary::Arbitrary], so
+/// `any::<str16>()` works as well.
+/// ```
+///  use no_std_strings::{str16, zstr};
+///  use proptest::test_runner::TestRunner;
+///  let mut runner = TestRunner::default();
+///  runner.run(&str16::any(), |s| { assert!(s.len() <= 15); Ok(()) }).unwrap();
+///  runner.run(&zstr::<8>::regex("[a-z]{1,5}").unwrap(), |s| {
+///    assert!(s.chars().all(|c| c.is_ascii_lowercase()));
+///    Ok(())
+///  }).unwrap();
+/// ```
+mod proptest_support {
+    use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
+    use proptest::collection::vec;
+    use super::*;
+    macro_rules! generate_impl {
+        ($ty: ident, $allow_nul: expr) => {
+            impl<const N: usize> $ty<N> {
+                /// proptest strategy generating arbitrary strings of up
+                /// to N-1 bytes.  Characters that do not fit are dropped,
+                /// so the generated string never exceeds capacity.
+                /// Requires `--features proptest`.
+                pub fn any() -> impl Strategy<Value = $ty<N>> {
+                    vec(any::<char>().prop_filter("NUL", |c| $allow_nul || *c != '\0'), 0..N)
+                        .prop_map(|cs| {
+                            let mut s = $ty::<N>::new();
+                            let mut buf = [0u8; 4];
+                            for c in cs {
+                                s.push(c.encode_utf8(&mut buf));
+                            }
+                            s
+                        })
+                }
+
+                /// proptest strategy generating strings that match the
+                /// given regular expression and fit within capacity.
+                /// Matches that are too long are rejected, not truncated.
+                /// Requires `--features proptest`.
+                #[allow(clippy::result_large_err)]
+                pub fn regex(pattern: &str) -> Result<impl Strategy<Value = $ty<N>>, proptest::string::Error> {
+                    Ok(proptest::string::string_regex(pattern)?
+                        .prop_filter_map("exceeds capacity", |s| {
+                            if !$allow_nul && s.contains('\0') { return None; }
+                            $ty::<N>::try_make(&s).ok()
+                        }))
+                }
+            }
+            impl<const N: usize> Arbitrary for $ty<N> {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<$ty<N>>;
+                fn arbitrary_with(_args: ()) -> Self::Strategy {
+                    $ty::<N>::any().boxed()
+                }
             }
         }
     }
-    generate_impl!(zstr, ZstrVisitor);
-    generate_impl!(tstr, TstrVisitor);
+    generate_impl!(zstr, false);
+    generate_impl!(tstr, true);
 }
 
 
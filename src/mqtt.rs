@@ -0,0 +1,197 @@
+//! MQTT topic names and filters: matching with the `+` and `#`
+//! wildcards, and building topics level by level in fixed strings.
+//! ```
+//!  use no_std_strings::mqtt::{topic_matches, Topic};
+//!  assert!(topic_matches("sensors/+/temp", "sensors/kitchen/temp"));
+//!  assert!(topic_matches("sensors/#", "sensors"));
+//!  assert!(!topic_matches("#", "$SYS/uptime"));
+//!  let mut t = Topic::<32>::new();
+//!  t.push_level("sensors").unwrap();
+//!  t.push_level("kitchen").unwrap();
+//!  assert_eq!(t, "sensors/kitchen");
+//!  assert!(t.matches("+/kitchen"));
+//! ```
+
+use crate::{tstr, CapacityError};
+use core::ops::Deref;
+
+/// Errors reported when validating topics and filters
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TopicError {
+    /// topics and filters must not be empty
+    Empty,
+    /// a wildcard in a topic name, a wildcard not occupying a whole
+    /// level, or `#` not at the end of a filter
+    InvalidWildcard,
+    /// a level pushed to a [Topic] contains `/`
+    InvalidLevel,
+    /// the topic does not fit in the fixed string
+    ExceedsCapacity,
+}
+
+impl core::fmt::Display for TopicError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            TopicError::Empty => "empty topic",
+            TopicError::InvalidWildcard => "misplaced topic wildcard",
+            TopicError::InvalidLevel => "topic level contains '/'",
+            TopicError::ExceedsCapacity => "topic exceeds capacity",
+        })
+    }
+}
+
+impl From<CapacityError> for TopicError {
+    fn from(_: CapacityError) -> Self {
+        TopicError::ExceedsCapacity
+    }
+}
+
+/// checks that a topic filter is well formed: wildcards occupy whole
+/// levels and `#` can only be the last level
+pub fn validate_filter(filter: &str) -> Result<(), TopicError> {
+    if filter.is_empty() {
+        return Err(TopicError::Empty);
+    }
+    let mut levels = filter.split('/').peekable();
+    while let Some(level) = levels.next() {
+        let ok = match level {
+            "+" => true,
+            "#" => levels.peek().is_none(),
+            _ => !level.contains(['+', '#']),
+        };
+        if !ok {
+            return Err(TopicError::InvalidWildcard);
+        }
+    }
+    Ok(())
+} //validate_filter
+
+/// checks that a topic name is non-empty and contains no wildcards
+pub fn validate_topic(topic: &str) -> Result<(), TopicError> {
+    if topic.is_empty() {
+        Err(TopicError::Empty)
+    } else if topic.contains(['+', '#']) {
+        Err(TopicError::InvalidWildcard)
+    } else {
+        Ok(())
+    }
+}
+
+/// returns true if the topic name matches the filter.  `+` matches
+/// exactly one level and a final `#` matches any number of levels,
+/// including none.  As required by the MQTT specification, topics
+/// beginning with `$` are not matched by a leading wildcard.  Malformed
+/// filters and topics never match.
+/// ```
+///  use no_std_strings::mqtt::topic_matches;
+///  assert!(topic_matches("a/+/c", "a//c"));
+///  assert!(topic_matches("a/#", "a/b/c"));
+///  assert!(!topic_matches("a/+", "a/b/c"));
+///  assert!(!topic_matches("a/b#", "a/b"));
+///  assert!(topic_matches("$SYS/#", "$SYS/uptime"));
+/// ```
+pub fn topic_matches(filter: &str, topic: &str) -> bool {
+    if validate_filter(filter).is_err() || validate_topic(topic).is_err() {
+        return false;
+    }
+    if topic.starts_with('$') && filter.starts_with(['+', '#']) {
+        return false;
+    }
+    let mut t = topic.split('/');
+    for f in filter.split('/') {
+        match (f, t.next()) {
+            ("#", _) => return true,
+            ("+", Some(_)) => {}
+            (f, Some(l)) if f == l => {}
+            _ => return false,
+        }
+    }
+    t.next().is_none()
+} //topic_matches
+
+/// A valid MQTT topic name in a fixed string of capacity N-1 bytes.
+/// It derefs to `str` and compares with `str`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Topic<const N: usize>(tstr<N>);
+
+impl<const N: usize> Topic<N> {
+    /// creates an empty topic, to be built with [Topic::push_level]
+    pub fn new() -> Self {
+        Topic(tstr::new())
+    }
+
+    /// validates a topic name and copies it, without truncation
+    pub fn parse(s: &str) -> Result<Self, TopicError> {
+        validate_topic(s)?;
+        Ok(Topic(tstr::try_make(s).map_err(|_| TopicError::ExceedsCapacity)?))
+    }
+
+    /// appends a level, preceded by a `/` unless the topic is empty.
+    /// On error the topic is unchanged.
+    /// ```
+    ///  use no_std_strings::mqtt::{Topic, TopicError};
+    ///  let mut t = Topic::<8>::parse("dev").unwrap();
+    ///  assert_eq!(t.push_level("a/b"), Err(TopicError::InvalidLevel));
+    ///  assert_eq!(t.push_level("+"), Err(TopicError::InvalidWildcard));
+    ///  assert_eq!(t.push_level("long"), Err(TopicError::ExceedsCapacity));
+    ///  t.push_level("7").unwrap();
+    ///  assert_eq!(t, "dev/7");
+    /// ```
+    pub fn push_level(&mut self, level: &str) -> Result<(), TopicError> {
+        if level.contains('/') {
+            return Err(TopicError::InvalidLevel);
+        }
+        if level.contains(['+', '#']) {
+            return Err(TopicError::InvalidWildcard);
+        }
+        let sep = if self.0.is_empty() { 0 } else { 1 };
+        if self.0.len() + sep + level.len() > N - 1 {
+            return Err(TopicError::ExceedsCapacity);
+        }
+        if sep == 1 {
+            self.0.push("/");
+        }
+        self.0.push(level);
+        Ok(())
+    } //push_level
+
+    /// iterates over the levels of the topic
+    pub fn levels(&self) -> core::str::Split<'_, char> {
+        self.0.to_str().split('/')
+    }
+
+    /// returns true if the topic matches the filter, see [topic_matches]
+    pub fn matches(&self, filter: &str) -> bool {
+        topic_matches(filter, self.0.to_str())
+    }
+
+    /// returns the underlying fixed string
+    pub fn into_inner(self) -> tstr<N> {
+        self.0
+    }
+} //impl Topic
+
+impl<const N: usize> Default for Topic<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for Topic<N> {
+    type Target = str;
+    fn deref(&self) -> &str {
+        self.0.to_str()
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for Topic<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0.to_str() == *other
+    }
+}
+
+impl<const N: usize> core::fmt::Display for Topic<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad(self.0.to_str())
+    }
+}
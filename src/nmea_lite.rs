@@ -0,0 +1,106 @@
+//! Validation and splitting of NMEA 0183 sentences from GPS receivers.
+//! ```
+//!  use no_std_strings::nmea_lite::Sentence;
+//!  let line = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47\r\n";
+//!  let s = Sentence::parse(line).unwrap();
+//!  assert_eq!((s.talker(), s.kind()), ("GP", "GGA"));
+//!  assert_eq!(s.field::<16>(2).unwrap(), "4807.038");
+//!  assert_eq!(s.fields().nth(9), Some("545.4"));
+//!  assert_eq!(s.field::<16>(13).unwrap(), "");
+//!  assert_eq!(s.fields().count(), 15);
+//! ```
+
+use crate::{str128, tstr};
+
+/// Errors returned by [Sentence::parse]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NmeaError {
+    /// the sentence does not start with `$` or `!`
+    MissingStart,
+    /// there is no `*` followed by two hex digits at the end
+    MissingChecksum,
+    /// the checksum does not match the XOR of the sentence bytes
+    ChecksumMismatch { expected: u8, computed: u8 },
+    /// the sentence does not fit in a str128
+    TooLong,
+}
+
+impl core::fmt::Display for NmeaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            NmeaError::MissingStart => f.write_str("NMEA sentence must start with '$' or '!'"),
+            NmeaError::MissingChecksum => f.write_str("missing NMEA checksum"),
+            NmeaError::ChecksumMismatch { expected, computed } => {
+                write!(f, "NMEA checksum {:02X} does not match computed {:02X}", expected, computed)
+            }
+            NmeaError::TooLong => f.write_str("NMEA sentence too long"),
+        }
+    }
+}
+
+/// A sentence with valid checksum, stored without the leading `$`, the
+/// checksum and the line ending
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Sentence {
+    body: str128,
+}
+
+impl Sentence {
+    /// validates the checksum of a sentence and copies it.  A trailing
+    /// `\r\n` is ignored.
+    /// ```
+    ///  use no_std_strings::nmea_lite::{NmeaError, Sentence};
+    ///  assert_eq!(Sentence::parse("$GPGLL,4916.45,N*00"),
+    ///             Err(NmeaError::ChecksumMismatch { expected: 0, computed: 0x3B }));
+    ///  assert_eq!(Sentence::parse("$GPGLL,4916.45,N"), Err(NmeaError::MissingChecksum));
+    ///  assert!(Sentence::parse("$GPGLL,4916.45,N*3b").is_ok());
+    /// ```
+    pub fn parse(line: &str) -> Result<Sentence, NmeaError> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let rest = line.strip_prefix(['$', '!']).ok_or(NmeaError::MissingStart)?;
+        let (body, sum) = rest.rsplit_once('*').ok_or(NmeaError::MissingChecksum)?;
+        if sum.len() != 2 || !sum.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(NmeaError::MissingChecksum);
+        }
+        let expected = u8::from_str_radix(sum, 16).map_err(|_| NmeaError::MissingChecksum)?;
+        let computed = body.bytes().fold(0, |a, b| a ^ b);
+        if expected != computed {
+            return Err(NmeaError::ChecksumMismatch { expected, computed });
+        }
+        let body = str128::try_make(body).map_err(|_| NmeaError::TooLong)?;
+        Ok(Sentence { body })
+    } //parse
+
+    /// the address field, such as `GPGGA`
+    pub fn address(&self) -> &str {
+        self.fields().next().unwrap_or("")
+    }
+
+    /// the talker identifier, such as `GP` or `GN`; empty for
+    /// proprietary `P` sentences
+    pub fn talker(&self) -> &str {
+        let a = self.address();
+        if a.starts_with('P') { "" } else { a.get(..2).unwrap_or("") }
+    }
+
+    /// the sentence type following the talker, such as `GGA`
+    pub fn kind(&self) -> &str {
+        &self.address()[self.talker().len()..]
+    }
+
+    /// iterates over the comma separated fields, starting with the address
+    pub fn fields(&self) -> core::str::Split<'_, char> {
+        self.body.to_str().split(',')
+    }
+
+    /// copies field n (the address is field 0) into a tstr\<M\>.  Returns
+    /// None if there is no such field or it does not fit.
+    pub fn field<const M: usize>(&self, n: usize) -> Option<tstr<M>> {
+        tstr::try_make(self.fields().nth(n)?).ok()
+    }
+
+    /// the sentence without `$`, checksum and line ending
+    pub fn as_str(&self) -> &str {
+        self.body.to_str()
+    }
+} //impl Sentence
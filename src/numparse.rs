@@ -0,0 +1,84 @@
+//! Integer parsing with radix prefixes, as found in config files and
+//! console input.
+
+use crate::{tstr, zstr};
+use core::num::ParseIntError;
+
+// ParseIntError cannot be constructed directly, so each kind is
+// obtained from a string that fails to parse in that way
+fn int_error(s: &str) -> ParseIntError {
+    s.parse::<i64>().unwrap_err()
+}
+
+// digits are accumulated one at a time, with the sign applied to each
+// so that i64::MIN can be reached; `_` separators are skipped
+pub(crate) fn parse_int_auto(s: &str) -> Result<i64, ParseIntError> {
+    let s = s.trim();
+    let (neg, rest) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (radix, digits) = match rest.as_bytes() {
+        [b'0', b'x' | b'X', ..] => (16, &rest[2..]),
+        [b'0', b'o' | b'O', ..] => (8, &rest[2..]),
+        [b'0', b'b' | b'B', ..] => (2, &rest[2..]),
+        _ => (10, rest),
+    };
+    let mut value: i64 = 0;
+    let mut any = false;
+    for c in digits.chars().filter(|c| *c != '_') {
+        let d = c.to_digit(radix).ok_or_else(|| int_error("_"))? as i64;
+        let v = value.checked_mul(radix as i64);
+        let v = if neg { v.and_then(|v| v.checked_sub(d)) } else { v.and_then(|v| v.checked_add(d)) };
+        value = match v {
+            Some(v) => v,
+            None if neg => return Err(int_error("-9223372036854775809")),
+            None => return Err(int_error("9223372036854775808")),
+        };
+        any = true;
+    }
+    if !any {
+        return Err(int_error(""));
+    }
+    Ok(value)
+} //parse_int_auto
+
+impl<const N: usize> zstr<N> {
+    /// parses the string as an i64, recognizing the radix prefixes
+    /// `0x`, `0o` and `0b`, an optional sign before the prefix, and `_`
+    /// separators between digits.  Surrounding whitespace is ignored.
+    /// ```
+    ///  use no_std_strings::zstr;
+    ///  assert_eq!(zstr::<16>::from("0x1F").parse_int_auto(), Ok(31));
+    ///  assert_eq!(zstr::<16>::from("-0b1010").parse_int_auto(), Ok(-10));
+    ///  assert_eq!(zstr::<16>::from(" 1_000_000\r\n").parse_int_auto(), Ok(1000000));
+    ///  assert!(zstr::<16>::from("0x-5").parse_int_auto().is_err());
+    ///  let mut long = zstr::<512>::new();
+    ///  for _ in 0..300 { long.push("0"); }
+    ///  long.push("7");
+    ///  assert_eq!(long.parse_int_auto(), Ok(7));
+    /// ```
+    pub fn parse_int_auto(&self) -> Result<i64, ParseIntError> {
+        parse_int_auto(self.to_str())
+    }
+}
+
+impl<const N: usize> tstr<N> {
+    /// parses the string as an i64, recognizing the radix prefixes
+    /// `0x`, `0o` and `0b`, an optional sign before the prefix, and `_`
+    /// separators between digits.  Surrounding whitespace is ignored.
+    /// ```
+    ///  use no_std_strings::str32;
+    ///  assert_eq!(str32::from("0o755").parse_int_auto(), Ok(493));
+    ///  assert_eq!(str32::from("+0XFF_FF").parse_int_auto(), Ok(65535));
+    ///  assert_eq!(str32::from("-0x8000_0000_0000_0000").parse_int_auto(), Ok(i64::MIN));
+    ///  assert!(str32::from("0x").parse_int_auto().is_err());
+    ///  use core::num::IntErrorKind;
+    ///  let e = str32::from("-0x8000_0000_0000_0001").parse_int_auto().unwrap_err();
+    ///  assert_eq!(e.kind(), &IntErrorKind::NegOverflow);
+    /// ```
+    pub fn parse_int_auto(&self) -> Result<i64, ParseIntError> {
+        parse_int_auto(self.to_str())
+    }
+}
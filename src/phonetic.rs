@@ -0,0 +1,85 @@
+//! Phonetic codes for fuzzy matching of names.
+
+use crate::{str8, tstr, zstr};
+
+// soundex digit of an upper-case ascii letter: b'0' for vowels, which
+// separate equal codes, and 0 for H and W, which do not
+fn soundex_code(b: u8) -> u8 {
+    match b {
+        b'B' | b'F' | b'P' | b'V' => b'1',
+        b'C' | b'G' | b'J' | b'K' | b'Q' | b'S' | b'X' | b'Z' => b'2',
+        b'D' | b'T' => b'3',
+        b'L' => b'4',
+        b'M' | b'N' => b'5',
+        b'R' => b'6',
+        b'H' | b'W' => 0,
+        _ => b'0',
+    }
+}
+
+/// American Soundex code of the ascii letters in s; other characters are
+/// ignored.  Returns an empty string if s contains no ascii letters.
+pub(crate) fn soundex(s: &str) -> str8 {
+    let mut code = [b'0'; 4];
+    let mut n = 0;
+    let mut last = 0;
+    for b in s.bytes().filter(u8::is_ascii_alphabetic) {
+        let b = b.to_ascii_uppercase();
+        let d = soundex_code(b);
+        if n == 0 {
+            code[0] = b;
+            n = 1;
+        } else if d == b'0' {
+            last = d;
+            continue;
+        } else if d != 0 && d != last {
+            code[n] = d;
+            n += 1;
+            if n == 4 {
+                break;
+            }
+        }
+        if d != 0 {
+            last = d;
+        }
+    }
+    if n == 0 {
+        return str8::new();
+    }
+    // the code consists only of ascii letters and digits
+    str8::make(core::str::from_utf8(&code).unwrap_or(""))
+} //soundex
+
+impl<const N: usize> zstr<N> {
+    /// returns the American Soundex code of the string, computed over its
+    /// ascii letters, for fuzzy matching of names.  Similar sounding names
+    /// share the same code.  The result is a str8 because the
+    /// four-character code does not fit in a str4, which holds at most
+    /// three bytes.
+    /// ```
+    ///  use no_std_strings::zstr;
+    ///  assert_eq!(zstr::<16>::from("Robert").soundex(), "R163");
+    ///  assert_eq!(zstr::<16>::from("Rupert").soundex(), "R163");
+    ///  assert_eq!(zstr::<16>::from("Ashcraft").soundex(), "A261");
+    /// ```
+    pub fn soundex(&self) -> str8 {
+        soundex(self.to_str())
+    }
+}
+
+impl<const N: usize> tstr<N> {
+    /// returns the American Soundex code of the string, computed over its
+    /// ascii letters.  Returns an empty string if there are none.  The
+    /// result is a str8 because the four-character code does not fit in
+    /// a str4, which holds at most three bytes.
+    /// ```
+    ///  use no_std_strings::str16;
+    ///  assert_eq!(str16::from("Tymczak").soundex(), "T522");
+    ///  assert_eq!(str16::from("Pfister").soundex(), "P236");
+    ///  assert_eq!(str16::from("Lee").soundex(), "L000");
+    ///  assert_eq!(str16::from("42").soundex(), "");
+    /// ```
+    pub fn soundex(&self) -> str8 {
+        soundex(self.to_str())
+    }
+}
@@ -0,0 +1,150 @@
+//! ANSI styling for embedded command-line interfaces.  A string is
+//! wrapped with color and attribute escape codes and rendered into a
+//! larger fixed string, or written directly through [core::fmt::Display].
+//! ```
+//!  use no_std_strings::{str8, str32};
+//!  use no_std_strings::style::Color;
+//!  let s = str8::from("FAIL");
+//!  let out = s.styled().fg(Color::Red).bold().render::<str32>().unwrap();
+//!  assert_eq!(out, "\x1b[1;31mFAIL\x1b[0m");
+//!  assert_eq!(out.strip_ansi(), "FAIL");
+//! ```
+
+use crate::{tstr, zstr, CapacityError};
+use core::fmt::Write;
+
+/// The standard terminal colors
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl Color {
+    // SGR code of the color as foreground; background codes are 10 higher
+    fn code(self) -> u8 {
+        let i = self as u8;
+        if i < 8 { 30 + i } else { 90 + i - 8 }
+    }
+}
+
+const BOLD: u8 = 1;
+const DIM: u8 = 2;
+const ITALIC: u8 = 4;
+const UNDERLINE: u8 = 8;
+const INVERSE: u8 = 16;
+// SGR codes of the attribute bits above, in order
+const ATTR_CODES: [u8; 5] = [1, 2, 3, 4, 7];
+
+/// A string with styling, created by `styled()` on the string types or
+/// by [Styled::new].  Displaying it writes the escape codes, the text,
+/// and a reset code.  Without any styling only the text is written.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Styled<'a> {
+    text: &'a str,
+    fg: Option<Color>,
+    bg: Option<Color>,
+    attrs: u8,
+}
+
+impl<'a> Styled<'a> {
+    /// wraps text without any styling
+    pub fn new(text: &'a str) -> Self {
+        Styled { text, fg: None, bg: None, attrs: 0 }
+    }
+    /// sets the foreground color
+    pub fn fg(mut self, c: Color) -> Self {
+        self.fg = Some(c);
+        self
+    }
+    /// sets the background color
+    pub fn bg(mut self, c: Color) -> Self {
+        self.bg = Some(c);
+        self
+    }
+    /// bold or increased intensity
+    pub fn bold(mut self) -> Self {
+        self.attrs |= BOLD;
+        self
+    }
+    /// faint or decreased intensity
+    pub fn dim(mut self) -> Self {
+        self.attrs |= DIM;
+        self
+    }
+    /// italic, not supported by all terminals
+    pub fn italic(mut self) -> Self {
+        self.attrs |= ITALIC;
+        self
+    }
+    /// underlined text
+    pub fn underline(mut self) -> Self {
+        self.attrs |= UNDERLINE;
+        self
+    }
+    /// swaps foreground and background colors
+    pub fn inverse(mut self) -> Self {
+        self.attrs |= INVERSE;
+        self
+    }
+
+    /// renders the styled text into a fixed string of type T.  Unlike
+    /// [str_format](crate::str_format)!, the output is never truncated,
+    /// since a cut escape sequence would garble the terminal.
+    pub fn render<T: Default + Write>(&self) -> Result<T, CapacityError> {
+        let mut t = T::default();
+        write!(t, "{}", self).map_err(|_| CapacityError)?;
+        Ok(t)
+    }
+} //impl Styled
+
+impl core::fmt::Display for Styled<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.fg.is_none() && self.bg.is_none() && self.attrs == 0 {
+            return f.write_str(self.text);
+        }
+        let mut sep = "\x1b[";
+        for (i, code) in ATTR_CODES.iter().enumerate() {
+            if self.attrs & (1 << i) != 0 {
+                write!(f, "{}{}", sep, code)?;
+                sep = ";";
+            }
+        }
+        if let Some(c) = self.fg {
+            write!(f, "{}{}", sep, c.code())?;
+            sep = ";";
+        }
+        if let Some(c) = self.bg {
+            write!(f, "{}{}", sep, c.code() + 10)?;
+        }
+        write!(f, "m{}\x1b[0m", self.text)
+    }
+}
+
+impl<const N: usize> zstr<N> {
+    /// starts styling the string with ANSI escape codes, see [Styled]
+    pub fn styled(&self) -> Styled<'_> {
+        Styled::new(self.to_str())
+    }
+}
+
+impl<const N: usize> tstr<N> {
+    /// starts styling the string with ANSI escape codes, see [Styled]
+    pub fn styled(&self) -> Styled<'_> {
+        Styled::new(self.to_str())
+    }
+}
@@ -0,0 +1,79 @@
+//! Runtime substitution of named placeholders, for message templates
+//! stored in flash.
+
+use crate::tstr;
+use core::fmt::Write;
+
+/// Error returned by [render_template].  Positions are byte offsets
+/// into the template.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TemplateError {
+    /// the lookup function returned None for the placeholder at this
+    /// position
+    UnknownPlaceholder(usize),
+    /// a `{` without closing `}`, or a single `}`, at this position
+    UnmatchedBrace(usize),
+    /// the rendered string exceeds capacity
+    ExceedsCapacity,
+}
+
+impl core::fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TemplateError::UnknownPlaceholder(i) => write!(f, "unknown placeholder at position {}", i),
+            TemplateError::UnmatchedBrace(i) => write!(f, "unmatched brace at position {}", i),
+            TemplateError::ExceedsCapacity => f.write_str("rendered template exceeds capacity"),
+        }
+    }
+}
+
+/// renders a template into a string of capacity M-1, replacing each
+/// `{name}` placeholder with the value returned by `lookup(name)`.  Braces
+/// are written literally as `{{` and `}}`.
+/// ```
+///  use no_std_strings::{render_template, str64, TemplateError};
+///  let lookup = |name: &str| match name {
+///    "ssid" => Some("home-net"),
+///    "rssi" => Some("-61"),
+///    _ => None,
+///  };
+///  let s: str64 = render_template("joined {ssid} ({rssi} dBm) {{ok}}", lookup).unwrap();
+///  assert_eq!(s, "joined home-net (-61 dBm) {ok}");
+///  assert_eq!(render_template::<64, _>("{ip}", lookup), Err(TemplateError::UnknownPlaceholder(0)));
+///  assert_eq!(render_template::<64, _>("x {ssid", lookup), Err(TemplateError::UnmatchedBrace(2)));
+/// ```
+pub fn render_template<'a, const M: usize, F>(template: &str, lookup: F) -> Result<tstr<M>, TemplateError>
+where
+    F: Fn(&str) -> Option<&'a str>,
+{
+    let mut out = tstr::<M>::new();
+    let bytes = template.as_bytes();
+    let (mut i, mut lit) = (0, 0); // lit: start of pending literal text
+    while i < bytes.len() {
+        let (b, next) = (bytes[i], bytes.get(i + 1).copied());
+        if b != b'{' && b != b'}' {
+            i += 1;
+            continue;
+        }
+        out.write_str(&template[lit..i]).map_err(|_| TemplateError::ExceedsCapacity)?;
+        if next == Some(b) {
+            // escaped brace: the second one starts the next literal
+            lit = i + 1;
+            i += 2;
+            continue;
+        }
+        if b == b'}' {
+            return Err(TemplateError::UnmatchedBrace(i));
+        }
+        let end = match template[i + 1..].find(['{', '}']) {
+            Some(k) if bytes[i + 1 + k] == b'}' => i + 1 + k,
+            _ => return Err(TemplateError::UnmatchedBrace(i)),
+        };
+        let value = lookup(&template[i + 1..end]).ok_or(TemplateError::UnknownPlaceholder(i))?;
+        out.write_str(value).map_err(|_| TemplateError::ExceedsCapacity)?;
+        i = end + 1;
+        lit = i;
+    }
+    out.write_str(&template[lit..]).map_err(|_| TemplateError::ExceedsCapacity)?;
+    Ok(out)
+} //render_template
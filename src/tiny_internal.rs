@@ -10,10 +10,10 @@
 #![allow(unused_mut)]
 #![allow(unused_imports)]
 #![allow(dead_code)]
-use crate::{str12, str128, str16, str192, str24, str256, str32, str4, str48, str64, str8, str96,zstr};
+use crate::{str12, str128, str16, str192, str24, str256, str32, str4, str48, str64, str8, str96,zstr,CapacityError};
 use core::cmp::{min, Ordering};
 use core::ops::{Add,Range,Index,IndexMut,RangeFull,RangeFrom,RangeTo};
-use core::ops::{RangeInclusive,RangeToInclusive};
+use core::ops::{Bound,RangeInclusive,RangeToInclusive};
 
 /// **THIS STRUCTURE IS NOT EXPORTED.**  It can only be referenced with the
 /// public type aliases [str4] through [str256].  This is to ensure that
@@ -76,6 +76,57 @@ impl<const N: usize> tstr<N> {
         tstr::make("")
     }
 
+    /// const version of [tstr::make], for building strings in const
+    /// contexts.  Panics if s exceeds capacity, which is a compile-time
+    /// error when evaluated in a const item.
+    pub const fn const_make(s: &str) -> tstr<N> {
+        let bytes = s.as_bytes();
+        if bytes.len() >= N {
+            panic!("length of string exceeds capacity in tstr::const_make");
+        }
+        let mut chars = [0u8; N];
+        let mut i = 0;
+        while i < bytes.len() {
+            chars[i + 1] = bytes[i];
+            i += 1;
+        }
+        chars[0] = bytes.len() as u8;
+        tstr { chrs: chars }
+    } //const_make
+
+    /// const fn equality, for comparisons at compile time
+    pub const fn const_eq(&self, other: &Self) -> bool {
+        matches!(self.const_cmp(other), Ordering::Equal)
+    }
+
+    /// const fn comparison, consistent with the [Ord] implementation.
+    /// Allows static assertions on, for example, the order of const
+    /// lookup tables.
+    /// ```
+    ///  use no_std_strings::str8;
+    ///  const TABLE: [str8; 3] = [str8::const_make("alpha"), str8::const_make("beta"), str8::const_make("gamma")];
+    ///  const _: () = assert!(TABLE[0].const_cmp(&TABLE[1]).is_lt() && TABLE[1].const_cmp(&TABLE[2]).is_lt());
+    ///  const _: () = assert!(!TABLE[0].const_eq(&TABLE[2]));
+    ///  assert!(TABLE[0] < TABLE[1]);
+    /// ```
+    pub const fn const_cmp(&self, other: &Self) -> Ordering {
+        let (la, lb) = (self.chrs[0] as usize, other.chrs[0] as usize);
+        let mut i = 1;
+        while i <= la && i <= lb {
+            if self.chrs[i] != other.chrs[i] {
+                return if self.chrs[i] < other.chrs[i] { Ordering::Less } else { Ordering::Greater };
+            }
+            i += 1;
+        }
+        if la < lb {
+            Ordering::Less
+        } else if la > lb {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    } //const_cmp
+
     /// length of the string in bytes (consistent with [str::len]). This
     /// is a constant-time operation.
     pub fn len(&self) -> usize {
@@ -172,6 +223,12 @@ impl<const N: usize> tstr<N> {
         self.chrs[n + 1] as char
     }
 
+    /// returns the byte at position i of the string, or None if i is not
+    /// less than the length of the string
+    pub fn get_byte(&self, i: usize) -> Option<u8> {
+        self.as_bytes().get(i).copied()
+    }
+
     /// determines if string is an ascii string
     pub fn is_ascii(&self) -> bool {
         self.to_str().is_ascii()
@@ -180,20 +237,34 @@ impl<const N: usize> tstr<N> {
     /// shortens the tstr in-place (mutates).  n indicates the number of
     /// *characters* to keep in thestring. If n is greater than the
     /// current character-length ([Self::charlen]) of the string, this operation will have no effect.
-    pub fn truncate(&mut self, n: usize) // n is char position, not binary position
+    /// Returns the number of bytes removed.
+    /// ```
+    ///  use no_std_strings::str16;
+    ///  let mut s = str16::from("aλbc");
+    ///  assert_eq!(s.truncate(2), 2);
+    ///  assert_eq!(s, "aλ");
+    ///  assert_eq!(s.truncate(5), 0);
+    /// ```
+    pub fn truncate(&mut self, n: usize) -> usize // n is char position, not binary position
     {
+        let len = self.len();
         if let Some((bi, c)) = self.to_str().char_indices().nth(n) {
             self.chrs[0] = bi as u8;
+            return len - bi;
         }
+        0
     }
     
     /// truncates string up to *byte* position n.  **Panics** if n is
-    /// not on a character boundary.
-    pub fn truncate_bytes(&mut self, n: usize) {
-       if (n<self.chrs[0] as usize) {
+    /// not on a character boundary.  Returns the number of bytes removed.
+    pub fn truncate_bytes(&mut self, n: usize) -> usize {
+       let len = self.chrs[0] as usize;
+       if (n<len) {
          assert!(self.is_char_boundary(n));
 	 self.chrs[0] = n as u8;
+         return len - n;
        }
+       0
     }
 
     /// resets string to empty string
@@ -290,7 +361,8 @@ impl<const N: usize> core::cmp::Ord for tstr<N> {
 impl<const M: usize> tstr<M> {
     /// converts an tstr\<M\> to an tstr\<N\>. If the length of the string being
     /// converted is greater than N, the extra characters will be ignored.
-    /// This operation produces a copy (non-destructive).
+    /// This operation produces a copy (non-destructive).  If truncated,
+    /// the string is cut at a character boundary.
     /// Example:
     ///```ignore
     ///  let s1:tstr<8> = tstr::from("abcdefg");
@@ -299,7 +371,8 @@ impl<const M: usize> tstr<M> {
     pub fn resize<const N: usize>(&self) -> tstr<N> {
         let slen = self.len();
         //if (slen>=N) {eprintln!("!Fixedstr Warning in str::resize: string \"{}\" truncated while resizing to str{}",self,N);}
-        let length = if (slen < N - 1) { slen } else { N - 1 };
+        let mut length = if (slen < N - 1) { slen } else { N - 1 };
+        while !self.is_char_boundary(length) { length -= 1; }
         let mut chars = [0u8; N];
         chars[1..length + 1].copy_from_slice(&self.chrs[1..length + 1]);
         //for i in 0..length {chars[i+1] = self.chrs[i+1];}
@@ -315,11 +388,36 @@ impl<const M: usize> tstr<M> {
             None
         }
     } //reallocate
+
+    /// version of resize that returns an error instead of truncating.
+    /// ```
+    ///  use no_std_strings::{str16, str8, CapacityError};
+    ///  let s = str16::from("config-v2");
+    ///  assert_eq!(s.resize_checked::<8>(), Err(CapacityError));
+    ///  let t: str8 = str16::from("cfg").resize_checked().unwrap();
+    ///  assert_eq!(t, "cfg");
+    /// ```
+    pub fn resize_checked<const N: usize>(&self) -> Result<tstr<N>, CapacityError> {
+        self.reallocate().ok_or(CapacityError)
+    }
+
+    /// version of resize that also returns the number of bytes dropped,
+    /// which is 0 if the string fits.
+    /// ```
+    ///  use no_std_strings::{str16, str8};
+    ///  let (t, dropped) = str16::from("config-v2").resize_reporting::<8>();
+    ///  assert_eq!((t, dropped), (str8::from("config-"), 2));
+    /// ```
+    pub fn resize_reporting<const N: usize>(&self) -> (tstr<N>, usize) {
+        let t = self.resize();
+        let dropped = self.len() - t.len();
+        (t, dropped)
+    }
 } //impl tstr<M>
 
 impl<const N: usize> core::fmt::Display for tstr<N> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}", self.to_str())
+        f.pad(self.to_str())
     }
 }
 
@@ -328,19 +426,53 @@ impl<const N: usize> PartialEq<&str> for tstr<N> {
         self.to_str() == *other // see below
     } //eq
 }
-impl<const N: usize> PartialEq<&str> for &tstr<N> {
-    fn eq(&self, other: &&str) -> bool {
-        &self.to_str() == other
-    } //eq
-}
 impl<'t, const N: usize> PartialEq<tstr<N>> for &'t str {
     fn eq(&self, other: &tstr<N>) -> bool {
         &other.to_str() == self
     }
 }
-impl<'t, const N: usize> PartialEq<&tstr<N>> for &'t str {
-    fn eq(&self, other: &&tstr<N>) -> bool {
-        &other.to_str() == self
+impl<const N: usize> PartialEq<str> for tstr<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.to_str() == other
+    }
+}
+impl<const N: usize> PartialEq<tstr<N>> for str {
+    fn eq(&self, other: &tstr<N>) -> bool {
+        other.to_str() == self
+    }
+}
+/// requires `--features alloc`
+/// ```
+///  use no_std_strings::str8;
+///  use std::borrow::Cow;
+///  let t = str8::from("abc");
+///  assert!(t == String::from("abc") && String::from("abc") == t);
+///  assert!(t != String::from("abd") && String::from("ab") != t);
+///  assert!(t == Cow::Borrowed("abc") && Cow::<str>::Owned("abc".into()) == t);
+///  assert!(t != Cow::Borrowed("x") && Cow::Borrowed("x") != t);
+/// ```
+#[cfg(feature = "alloc")]
+impl<const N: usize> PartialEq<alloc::string::String> for tstr<N> {
+    fn eq(&self, other: &alloc::string::String) -> bool {
+        self.to_str() == other.as_str()
+    }
+}
+#[cfg(feature = "alloc")]
+impl<const N: usize> PartialEq<tstr<N>> for alloc::string::String {
+    fn eq(&self, other: &tstr<N>) -> bool {
+        other.to_str() == self.as_str()
+    }
+}
+#[cfg(feature = "alloc")]
+impl<'t, const N: usize> PartialEq<alloc::borrow::Cow<'t, str>> for tstr<N> {
+    fn eq(&self, other: &alloc::borrow::Cow<'t, str>) -> bool {
+        self.to_str() == other.as_ref()
+    }
+}
+#[cfg(feature = "alloc")]
+impl<'t, const N: usize> PartialEq<tstr<N>> for alloc::borrow::Cow<'t, str> {
+    fn eq(&self, other: &tstr<N>) -> bool {
+        other.to_str() == self.as_ref()
     }
 }
 
@@ -362,6 +494,13 @@ impl<const N: usize, const M: usize> PartialEq<&zstr<N>> for tstr<M> {
     }
 }
 
+/// compares contents byte-wise, consistent with the Ord of each type
+impl<const N: usize, const M: usize> PartialOrd<zstr<N>> for tstr<M> {
+    fn partial_cmp(&self, other: &zstr<N>) -> Option<Ordering> {
+        Some(self.as_bytes().cmp(other.to_str().as_bytes()))
+    }
+}
+
 impl<const N: usize> core::fmt::Debug for tstr<N> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.pad(&self.to_str())
@@ -372,16 +511,36 @@ impl<const N: usize> core::fmt::Debug for tstr<N> {
 } // Debug impl
 
 
-///Convert fstr to &str slice
-impl<IndexType, const N: usize> core::ops::Index<IndexType> for tstr<N>
-where
-    IndexType: core::slice::SliceIndex<str>,
-{
-    type Output = IndexType::Output;
-    fn index(&self, index: IndexType) -> &Self::Output {
-        &self.to_str()[index]
+///Convert tstr to &str slice for each of the Range types
+macro_rules! index_range {
+    ($($range: ty),+) => {
+        $(impl<const N: usize> Index<$range> for tstr<N> {
+            type Output = str;
+            fn index(&self, index: $range) -> &Self::Output {
+                &self.to_str()[index]
+            }
+        })+
+    };
+}
+index_range!(Range<usize>, RangeTo<usize>, RangeFrom<usize>, RangeInclusive<usize>, RangeToInclusive<usize>, RangeFull);
+index_range!((Bound<usize>, Bound<usize>));
+
+///Index by usize returns a byte of the string.  **Panics** if the index
+///is not less than the length of the string.
+///```
+///  use no_std_strings::str16;
+///  let s = str16::from("$GPGGA");
+///  assert_eq!(s[0], b'$');
+///  assert_eq!(&s[1..3], "GP");
+///  use core::ops::Bound::{Excluded, Unbounded};
+///  assert_eq!(&s[(Excluded(2), Unbounded)], "GGA");
+///```
+impl<const N: usize> core::ops::Index<usize> for tstr<N> {
+    type Output = u8;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.as_bytes()[index]
     }
-} //impl Index
+}
 
 
 impl<const N: usize> tstr<N> {
@@ -418,6 +577,60 @@ impl<const N: usize> tstr<N> {
 
 
 
+/// concatenates an array of strings into a string of capacity M-1 in a
+/// const context.  Panics if the combined length exceeds capacity, which
+/// is a compile-time error when evaluated in a const item.
+/// ```
+///  use no_std_strings::{concat_all, str8, str32};
+///  const VENDOR: str8 = str8::const_make("acme");
+///  const BANNER: str32 = concat_all([VENDOR, str8::const_make("-"), str8::const_make("v1.2")]);
+///  assert_eq!(BANNER, "acme-v1.2");
+/// ```
+pub const fn concat_all<const K: usize, const N: usize, const M: usize>(parts: [tstr<N>; K]) -> tstr<M> {
+    let mut chars = [0u8; M];
+    let mut total = 0;
+    let mut k = 0;
+    while k < K {
+        let len = parts[k].chrs[0] as usize;
+        if total + len >= M {
+            panic!("combined length exceeds capacity in concat_all");
+        }
+        let mut i = 0;
+        while i < len {
+            chars[total + i + 1] = parts[k].chrs[i + 1];
+            i += 1;
+        }
+        total += len;
+        k += 1;
+    }
+    chars[0] = total as u8;
+    tstr { chrs: chars }
+} //concat_all
+
+impl str8 {
+    /// returns the string as a single word: the length byte followed by
+    /// the characters in little-endian order, with unused bytes zeroed.
+    /// Two str8 strings are equal exactly when their words are, which
+    /// [str_match](crate::str_match)! uses to dispatch on tokens.
+    /// ```
+    ///  use no_std_strings::str8;
+    ///  let mut a = str8::from("abcdef");
+    ///  a.truncate_bytes(2);
+    ///  assert_eq!(a.to_u64(), str8::from("ab").to_u64());
+    ///  assert_eq!(str8::const_make("ab").to_u64(), 0x6261_02);
+    /// ```
+    pub const fn to_u64(self) -> u64 {
+        let used = self.chrs[0] as u32 + 1;
+        let word = u64::from_le_bytes(self.chrs);
+        if used >= 8 { word } else { word & ((1u64 << (8 * used)) - 1) }
+    }
+
+    // inverse of to_u64, for words produced by it
+    pub(crate) const fn from_u64(word: u64) -> str8 {
+        tstr { chrs: word.to_le_bytes() }
+    }
+}
+
 impl Add for str8 {
     type Output = str16;
     fn add(self, other: Self) -> Self::Output {
@@ -0,0 +1,169 @@
+//! Unicode case-insensitive comparisons, enabled by `--features unicase`.
+//! [UniCaseStr] wraps any string type and compares, orders and hashes it
+//! by [simple case folding](https://www.unicode.org/reports/tr44/#CaseFolding.txt),
+//! which maps each character to exactly one character.  Unlike the ascii
+//! functions such as [zstr::make_ascii_lowercase](crate::zstr::make_ascii_lowercase),
+//! this treats, for example, `Σ`, `σ` and `ς` as equal.
+//! ```
+//!  use no_std_strings::{str32, zstr, UniCaseStr};
+//!  let a = UniCaseStr(str32::from("ΟΔΥΣΣΕΥΣ"));
+//!  let b = UniCaseStr(zstr::<32>::from("Οδυσσεύς"));
+//!  assert!(a != b);  // accents differ
+//!  assert_eq!(a, UniCaseStr(str32::from("οδυσσευς")));
+//!  assert_eq!(UniCaseStr("Straße"), UniCaseStr("STRAẞE"));
+//!  assert!(UniCaseStr("apple") < UniCaseStr("Banana"));
+//! ```
+
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+// runs of (first code point, length, stride, offset to folded code point),
+// generated from the simple (C+S) mappings of CaseFolding.txt
+const FOLD_RUNS: [(u32, u8, u8, i32); 202] = [
+    (0x0041, 26, 1, 32), (0x00B5, 1, 1, 775), (0x00C0, 23, 1, 32), (0x00D8, 7, 1, 32),
+    (0x0100, 24, 2, 1), (0x0132, 3, 2, 1), (0x0139, 8, 2, 1), (0x014A, 23, 2, 1),
+    (0x0178, 1, 1, -121), (0x0179, 3, 2, 1), (0x017F, 1, 1, -268), (0x0181, 1, 1, 210),
+    (0x0182, 2, 2, 1), (0x0186, 1, 1, 206), (0x0187, 1, 1, 1), (0x0189, 2, 1, 205),
+    (0x018B, 1, 1, 1), (0x018E, 1, 1, 79), (0x018F, 1, 1, 202), (0x0190, 1, 1, 203),
+    (0x0191, 1, 1, 1), (0x0193, 1, 1, 205), (0x0194, 1, 1, 207), (0x0196, 1, 1, 211),
+    (0x0197, 1, 1, 209), (0x0198, 1, 1, 1), (0x019C, 1, 1, 211), (0x019D, 1, 1, 213),
+    (0x019F, 1, 1, 214), (0x01A0, 3, 2, 1), (0x01A6, 1, 1, 218), (0x01A7, 1, 1, 1),
+    (0x01A9, 1, 1, 218), (0x01AC, 1, 1, 1), (0x01AE, 1, 1, 218), (0x01AF, 1, 1, 1),
+    (0x01B1, 2, 1, 217), (0x01B3, 2, 2, 1), (0x01B7, 1, 1, 219), (0x01B8, 1, 1, 1),
+    (0x01BC, 1, 1, 1), (0x01C4, 1, 1, 2), (0x01C5, 1, 1, 1), (0x01C7, 1, 1, 2),
+    (0x01C8, 1, 1, 1), (0x01CA, 1, 1, 2), (0x01CB, 9, 2, 1), (0x01DE, 9, 2, 1),
+    (0x01F1, 1, 1, 2), (0x01F2, 2, 2, 1), (0x01F6, 1, 1, -97), (0x01F7, 1, 1, -56),
+    (0x01F8, 20, 2, 1), (0x0220, 1, 1, -130), (0x0222, 9, 2, 1), (0x023A, 1, 1, 10795),
+    (0x023B, 1, 1, 1), (0x023D, 1, 1, -163), (0x023E, 1, 1, 10792), (0x0241, 1, 1, 1),
+    (0x0243, 1, 1, -195), (0x0244, 1, 1, 69), (0x0245, 1, 1, 71), (0x0246, 5, 2, 1),
+    (0x0345, 1, 1, 116), (0x0370, 2, 2, 1), (0x0376, 1, 1, 1), (0x037F, 1, 1, 116),
+    (0x0386, 1, 1, 38), (0x0388, 3, 1, 37), (0x038C, 1, 1, 64), (0x038E, 2, 1, 63),
+    (0x0391, 17, 1, 32), (0x03A3, 9, 1, 32), (0x03C2, 1, 1, 1), (0x03CF, 1, 1, 8),
+    (0x03D0, 1, 1, -30), (0x03D1, 1, 1, -25), (0x03D5, 1, 1, -15), (0x03D6, 1, 1, -22),
+    (0x03D8, 12, 2, 1), (0x03F0, 1, 1, -54), (0x03F1, 1, 1, -48), (0x03F4, 1, 1, -60),
+    (0x03F5, 1, 1, -64), (0x03F7, 1, 1, 1), (0x03F9, 1, 1, -7), (0x03FA, 1, 1, 1),
+    (0x03FD, 3, 1, -130), (0x0400, 16, 1, 80), (0x0410, 32, 1, 32), (0x0460, 17, 2, 1),
+    (0x048A, 27, 2, 1), (0x04C0, 1, 1, 15), (0x04C1, 7, 2, 1), (0x04D0, 48, 2, 1),
+    (0x0531, 38, 1, 48), (0x10A0, 38, 1, 7264), (0x10C7, 1, 1, 7264),
+    (0x10CD, 1, 1, 7264), (0x13F8, 6, 1, -8), (0x1C80, 1, 1, -6222),
+    (0x1C81, 1, 1, -6221), (0x1C82, 1, 1, -6212), (0x1C83, 2, 1, -6210),
+    (0x1C85, 1, 1, -6211), (0x1C86, 1, 1, -6204), (0x1C87, 1, 1, -6180),
+    (0x1C88, 1, 1, 35267), (0x1C90, 43, 1, -3008), (0x1CBD, 3, 1, -3008),
+    (0x1E00, 75, 2, 1), (0x1E9B, 1, 1, -58), (0x1E9E, 1, 1, -7615), (0x1EA0, 48, 2, 1),
+    (0x1F08, 8, 1, -8), (0x1F18, 6, 1, -8), (0x1F28, 8, 1, -8), (0x1F38, 8, 1, -8),
+    (0x1F48, 6, 1, -8), (0x1F59, 4, 2, -8), (0x1F68, 8, 1, -8), (0x1F88, 8, 1, -8),
+    (0x1F98, 8, 1, -8), (0x1FA8, 8, 1, -8), (0x1FB8, 2, 1, -8), (0x1FBA, 2, 1, -74),
+    (0x1FBC, 1, 1, -9), (0x1FBE, 1, 1, -7173), (0x1FC8, 4, 1, -86), (0x1FCC, 1, 1, -9),
+    (0x1FD8, 2, 1, -8), (0x1FDA, 2, 1, -100), (0x1FE8, 2, 1, -8), (0x1FEA, 2, 1, -112),
+    (0x1FEC, 1, 1, -7), (0x1FF8, 2, 1, -128), (0x1FFA, 2, 1, -126), (0x1FFC, 1, 1, -9),
+    (0x2126, 1, 1, -7517), (0x212A, 1, 1, -8383), (0x212B, 1, 1, -8262),
+    (0x2132, 1, 1, 28), (0x2160, 16, 1, 16), (0x2183, 1, 1, 1), (0x24B6, 26, 1, 26),
+    (0x2C00, 48, 1, 48), (0x2C60, 1, 1, 1), (0x2C62, 1, 1, -10743),
+    (0x2C63, 1, 1, -3814), (0x2C64, 1, 1, -10727), (0x2C67, 3, 2, 1),
+    (0x2C6D, 1, 1, -10780), (0x2C6E, 1, 1, -10749), (0x2C6F, 1, 1, -10783),
+    (0x2C70, 1, 1, -10782), (0x2C72, 1, 1, 1), (0x2C75, 1, 1, 1),
+    (0x2C7E, 2, 1, -10815), (0x2C80, 50, 2, 1), (0x2CEB, 2, 2, 1), (0x2CF2, 1, 1, 1),
+    (0xA640, 23, 2, 1), (0xA680, 14, 2, 1), (0xA722, 7, 2, 1), (0xA732, 31, 2, 1),
+    (0xA779, 2, 2, 1), (0xA77D, 1, 1, -35332), (0xA77E, 5, 2, 1), (0xA78B, 1, 1, 1),
+    (0xA78D, 1, 1, -42280), (0xA790, 2, 2, 1), (0xA796, 10, 2, 1),
+    (0xA7AA, 1, 1, -42308), (0xA7AB, 1, 1, -42319), (0xA7AC, 1, 1, -42315),
+    (0xA7AD, 1, 1, -42305), (0xA7AE, 1, 1, -42308), (0xA7B0, 1, 1, -42258),
+    (0xA7B1, 1, 1, -42282), (0xA7B2, 1, 1, -42261), (0xA7B3, 1, 1, 928),
+    (0xA7B4, 8, 2, 1), (0xA7C4, 1, 1, -48), (0xA7C5, 1, 1, -42307),
+    (0xA7C6, 1, 1, -35384), (0xA7C7, 2, 2, 1), (0xA7D0, 1, 1, 1), (0xA7D6, 2, 2, 1),
+    (0xA7F5, 1, 1, 1), (0xAB70, 80, 1, -38864), (0xFF21, 26, 1, 32),
+    (0x10400, 40, 1, 40), (0x104B0, 36, 1, 40), (0x10570, 11, 1, 39),
+    (0x1057C, 15, 1, 39), (0x1058C, 7, 1, 39), (0x10594, 2, 1, 39),
+    (0x10C80, 51, 1, 64), (0x118A0, 32, 1, 32), (0x16E40, 32, 1, 32),
+    (0x1E900, 34, 1, 34),
+];
+
+/// returns the simple case folding of a character, or the character
+/// itself if it has none
+pub fn simple_fold(c: char) -> char {
+    let cp = c as u32;
+    if cp < 0x41 {
+        return c;
+    }
+    if cp < 0x80 {
+        return c.to_ascii_lowercase();
+    }
+    let i = FOLD_RUNS.partition_point(|r| r.0 <= cp);
+    if i == 0 {
+        return c;
+    }
+    let (start, len, stride, delta) = FOLD_RUNS[i - 1];
+    let off = cp - start;
+    // `%` rather than is_multiple_of, which needs Rust 1.87
+    #[allow(unknown_lints, clippy::manual_is_multiple_of)]
+    let on_stride = off % stride as u32 == 0;
+    if on_stride && off / (stride as u32) < len as u32 {
+        char::from_u32((cp as i32 + delta) as u32).unwrap_or(c)
+    } else {
+        c
+    }
+} //simple_fold
+
+/// Wrapper comparing, ordering and hashing strings by Unicode simple case
+/// folding.  Works with any `T: AsRef<str>`, including all fixed string
+/// types, so that it can be used as a key in lookup tables.
+#[derive(Copy, Clone, Default)]
+pub struct UniCaseStr<T>(pub T);
+
+impl<T: AsRef<str>> UniCaseStr<T> {
+    /// returns the wrapped value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// iterator over the case-folded characters of the string
+    pub fn folded(&self) -> impl Iterator<Item = char> + '_ {
+        self.0.as_ref().chars().map(simple_fold)
+    }
+}
+
+impl<T> core::ops::Deref for UniCaseStr<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: AsRef<str>, U: AsRef<str>> PartialEq<UniCaseStr<U>> for UniCaseStr<T> {
+    fn eq(&self, other: &UniCaseStr<U>) -> bool {
+        self.folded().eq(other.folded())
+    }
+}
+impl<T: AsRef<str>> Eq for UniCaseStr<T> {}
+
+impl<T: AsRef<str>, U: AsRef<str>> PartialOrd<UniCaseStr<U>> for UniCaseStr<T> {
+    fn partial_cmp(&self, other: &UniCaseStr<U>) -> Option<Ordering> {
+        Some(self.folded().cmp(other.folded()))
+    }
+}
+impl<T: AsRef<str>> Ord for UniCaseStr<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.folded().cmp(other.folded())
+    }
+}
+
+impl<T: AsRef<str>> Hash for UniCaseStr<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut buf = [0u8; 4];
+        for c in self.folded() {
+            state.write(c.encode_utf8(&mut buf).as_bytes());
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl<T: core::fmt::Display> core::fmt::Display for UniCaseStr<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+impl<T: core::fmt::Debug> core::fmt::Debug for UniCaseStr<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -0,0 +1,127 @@
+//! Splitting of simple URLs into fixed string fields, for devices
+//! configured with HTTP or MQTT endpoints.  Only URLs of the form
+//! `scheme://host[:port][/path][?query][#fragment]` are accepted; user
+//! information is rejected and the fragment is dropped.
+//! ```
+//!  use no_std_strings::str256;
+//!  use no_std_strings::url_lite::{Url, UrlError};
+//!  let cfg = str256::from("MQTTS://broker.local:8884/dev/7?qos=1#x");
+//!  let u = Url::parse(&cfg).unwrap();
+//!  assert_eq!(u.scheme, "mqtts");
+//!  assert_eq!(u.host, "broker.local");
+//!  assert_eq!(u.port, Some(8884));
+//!  assert_eq!(u.path, "/dev/7");
+//!  assert_eq!(u.query, "qos=1");
+//!  let u = Url::parse("http://[fe80::1]").unwrap();
+//!  assert_eq!((u.host.as_str(), u.port_or_default()), ("[fe80::1]", Some(80)));
+//!  assert_eq!(Url::parse("host:80"), Err(UrlError::MissingScheme));
+//!  assert_eq!(Url::parse("http://h:99999/"), Err(UrlError::InvalidPort));
+//! ```
+
+use crate::{str128, str16, str64};
+
+/// Errors returned by [Url::parse]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UrlError {
+    /// there is no `scheme://` prefix
+    MissingScheme,
+    /// the scheme contains characters other than letters, digits, `+-.`
+    InvalidScheme,
+    /// the host is empty, has user information, or invalid characters
+    InvalidHost,
+    /// the port is empty, not a number, or out of range
+    InvalidPort,
+    /// a component does not fit in its field
+    TooLong,
+}
+
+impl core::fmt::Display for UrlError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            UrlError::MissingScheme => "missing URL scheme",
+            UrlError::InvalidScheme => "invalid URL scheme",
+            UrlError::InvalidHost => "invalid URL host",
+            UrlError::InvalidPort => "invalid URL port",
+            UrlError::TooLong => "URL component too long",
+        })
+    }
+}
+
+/// The components of a URL.  The scheme is converted to lower case,
+/// the path keeps its leading `/` and is empty if absent, and the query
+/// does not include the `?`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Url {
+    pub scheme: str16,
+    pub host: str64,
+    pub port: Option<u16>,
+    pub path: str128,
+    pub query: str128,
+}
+
+impl Url {
+    /// splits a URL into its components, usually called on a str256
+    pub fn parse(url: &str) -> Result<Url, UrlError> {
+        let (scheme, rest) = url.split_once("://").ok_or(UrlError::MissingScheme)?;
+        let mut sb = scheme.bytes();
+        if !matches!(sb.next(), Some(b) if b.is_ascii_alphabetic())
+            || !sb.all(|b| b.is_ascii_alphanumeric() || b"+-.".contains(&b))
+        {
+            return Err(UrlError::InvalidScheme);
+        }
+        let rest = rest.split('#').next().unwrap_or("");
+        let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (authority, path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, ""),
+        };
+        // an IPv6 literal host contains colons, so look for the port after ']'
+        let port_from = if authority.starts_with('[') {
+            authority.find(']').ok_or(UrlError::InvalidHost)? + 1
+        } else {
+            0
+        };
+        let (host, port) = match authority[port_from..].find(':') {
+            Some(i) => (&authority[..port_from + i], Some(&authority[port_from + i + 1..])),
+            None => (authority, None),
+        };
+        let host_ok = if let Some(inner) = host.strip_prefix('[') {
+            inner.len() > 1 && inner[..inner.len() - 1].bytes().all(|b| b.is_ascii_hexdigit() || b == b':' || b == b'.')
+        } else {
+            !host.is_empty() && host.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'.')
+        };
+        if !host_ok || port_from > 0 && port_from != host.len() {
+            return Err(UrlError::InvalidHost);
+        }
+        let port = match port {
+            Some(p) if !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()) => {
+                Some(p.parse::<u16>().map_err(|_| UrlError::InvalidPort)?)
+            }
+            Some(_) => return Err(UrlError::InvalidPort),
+            None => None,
+        };
+        let mut scheme = str16::try_make(scheme).map_err(|_| UrlError::TooLong)?;
+        scheme.make_ascii_lowercase();
+        Ok(Url {
+            scheme,
+            host: str64::try_make(host).map_err(|_| UrlError::TooLong)?,
+            port,
+            path: str128::try_make(path).map_err(|_| UrlError::TooLong)?,
+            query: str128::try_make(query).map_err(|_| UrlError::TooLong)?,
+        })
+    } //parse
+
+    /// returns the port, or the default port of well-known schemes
+    /// (http, https, ws, wss, mqtt, mqtts, coap, coaps) if absent
+    pub fn port_or_default(&self) -> Option<u16> {
+        self.port.or(match self.scheme.as_str() {
+            "http" | "ws" => Some(80),
+            "https" | "wss" => Some(443),
+            "mqtt" => Some(1883),
+            "mqtts" => Some(8883),
+            "coap" => Some(5683),
+            "coaps" => Some(5684),
+            _ => None,
+        })
+    }
+} //impl Url
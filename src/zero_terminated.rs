@@ -26,15 +26,17 @@
 #![allow(unused_assignments)]
 #![allow(unused_mut)]
 #![allow(dead_code)]
-use crate::{tstr};
+use crate::{tstr, CapacityError};
 use core::cmp::{min, Ordering};
+use core::mem::MaybeUninit;
 //use core::ops::{Range,RangeFull,RangeFrom,RangeTo};
 //use core::ops::{RangeInclusive,RangeToInclusive};
 
 /// `zstr<N>`: zero-terminated utf8 strings of size up to N bytes.  Note that
 /// zstr supports unicode, so that the length of string in characters may
-/// be less than N.
+/// be less than N.  The layout of `zstr<N>` is that of `[u8; N]`.
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[repr(transparent)]
 pub struct zstr<const N: usize> {
     chrs: [u8; N],
 } //zstr
@@ -87,6 +89,50 @@ impl<const N: usize> zstr<N> {
         zstr::make("")
     }
 
+    /// const version of [zstr::make], for building strings in const
+    /// contexts.  Panics if s exceeds capacity, which is a compile-time
+    /// error when evaluated in a const item.
+    pub const fn const_make(s: &str) -> zstr<N> {
+        let bytes = s.as_bytes();
+        if bytes.len() >= N {
+            panic!("length of string exceeds capacity in zstr::const_make");
+        }
+        let mut chars = [0u8; N];
+        let mut i = 0;
+        while i < bytes.len() {
+            chars[i] = bytes[i];
+            i += 1;
+        }
+        zstr { chrs: chars }
+    } //const_make
+
+    /// const fn equality, for comparisons at compile time
+    pub const fn const_eq(&self, other: &Self) -> bool {
+        matches!(self.const_cmp(other), Ordering::Equal)
+    }
+
+    /// const fn comparison, consistent with the [Ord] implementation.
+    /// ```
+    ///  use no_std_strings::zstr;
+    ///  const MODE: zstr<16> = zstr::const_make("release");
+    ///  const _: () = assert!(MODE.const_eq(&zstr::const_make("release")));
+    ///  const _: () = assert!(MODE.const_cmp(&zstr::const_make("debug")).is_gt());
+    /// ```
+    pub const fn const_cmp(&self, other: &Self) -> Ordering {
+        let mut i = 0;
+        while i < N {
+            let (a, b) = (self.chrs[i], other.chrs[i]);
+            if a != b {
+                return if a < b { Ordering::Less } else { Ordering::Greater };
+            }
+            if a == 0 {
+                break;
+            }
+            i += 1;
+        }
+        Ordering::Equal
+    } //const_cmp
+
 
   /// creates a new `zstr<N>` with given u8 slice.  If the length of s exceeds
   /// N, the extra characters are ignored.  The last byte of the array is
@@ -104,6 +150,125 @@ impl<const N: usize> zstr<N> {
      z
   }//from_raw
 
+    /// safe counterpart of [zstr::from_raw], mirroring
+    /// `CStr::from_bytes_until_nul`: the slice is scanned up to the first
+    /// zero byte, and the bytes before it must be valid utf8 that fits
+    /// within the capacity of `zstr<N>`.
+    /// ```
+    ///  use no_std_strings::{zstr, FromBytesUntilNulError};
+    ///  let field = b"sensor-1\0\xff\xff";
+    ///  assert_eq!(zstr::<16>::from_bytes_until_nul(field).unwrap(), "sensor-1");
+    ///  assert_eq!(zstr::<16>::from_bytes_until_nul(b"abc"),
+    ///             Err(FromBytesUntilNulError::NulNotFound));
+    ///  assert_eq!(zstr::<4>::from_bytes_until_nul(b"abcd\0"),
+    ///             Err(FromBytesUntilNulError::ExceedsCapacity));
+    /// ```
+    pub fn from_bytes_until_nul(bytes: &[u8]) -> Result<zstr<N>, FromBytesUntilNulError> {
+        let end = match bytes.iter().position(|b| *b == 0) {
+            Some(end) => end,
+            None => return Err(FromBytesUntilNulError::NulNotFound),
+        };
+        let s = core::str::from_utf8(&bytes[..end]).map_err(FromBytesUntilNulError::InvalidUtf8)?;
+        if end > N - 1 {
+            return Err(FromBytesUntilNulError::ExceedsCapacity);
+        }
+        Ok(zstr::create(s))
+    } //from_bytes_until_nul
+
+    /// copies the string into a C `char` array of the same size, such as
+    /// a `char name[32]` member of a bindgen-generated struct.  All bytes
+    /// after the string are zero.
+    /// ```
+    ///  use no_std_strings::zstr;
+    ///  use core::ffi::c_char;
+    ///  #[repr(C)]
+    ///  struct DeviceInfo { name: [c_char; 16], id: u32 }
+    ///  let name = zstr::<16>::from("probe");
+    ///  let info = DeviceInfo { name: name.to_c_array(), id: 7 };
+    ///  assert_eq!(info.name[..6], [b'p', b'r', b'o', b'b', b'e', 0].map(|b| b as c_char));
+    ///  assert_eq!(zstr::<16>::from_c_array(&info.name).unwrap(), "probe");
+    /// ```
+    pub fn to_c_array(&self) -> [core::ffi::c_char; N] {
+        let mut a = [0 as core::ffi::c_char; N];
+        for (c, b) in a.iter_mut().zip(self.as_bytes()) {
+            *c = *b as core::ffi::c_char;
+        }
+        a
+    }
+
+    /// copies a string from a C `char` array of the same size, as
+    /// [zstr::from_bytes_until_nul].  Fails with
+    /// [FromBytesUntilNulError::NulNotFound] if the array is not
+    /// zero-terminated.
+    pub fn from_c_array(a: &[core::ffi::c_char; N]) -> Result<zstr<N>, FromBytesUntilNulError> {
+        // c_char is i8 or u8, so the array has the layout of [u8; N]
+        let bytes = unsafe { &*(a as *const [core::ffi::c_char; N] as *const [u8; N]) };
+        zstr::from_bytes_until_nul(bytes)
+    }
+
+    /// returns an uninitialized `zstr<N>`, so that the buffer can be
+    /// filled in place (for example by a peripheral) and then converted
+    /// with [zstr::assume_init_with_len] or [zstr::init_from_reader],
+    /// without copying a finished string into it.
+    pub const fn uninit() -> MaybeUninit<zstr<N>> {
+        MaybeUninit::uninit()
+    }
+
+    /// declares the first `len` bytes of an uninitialized zstr to be its
+    /// contents.  The bytes from position `len` onwards are zeroed.
+    ///
+    /// # Safety
+    /// `len` must be less than N, and the first `len` bytes of `buf` must
+    /// have been initialized with valid utf8 that contains no zero byte.
+    /// ```
+    ///  use no_std_strings::zstr;
+    ///  let mut buf = zstr::<32>::uninit();
+    ///  let dst = buf.as_mut_ptr() as *mut u8;
+    ///  unsafe { core::ptr::copy_nonoverlapping(b"OK".as_ptr(), dst, 2); }
+    ///  let s = unsafe { zstr::assume_init_with_len(&mut buf, 2) };
+    ///  assert_eq!(*s, "OK");
+    /// ```
+    pub unsafe fn assume_init_with_len(buf: &mut MaybeUninit<zstr<N>>, len: usize) -> &mut zstr<N> {
+        debug_assert!(len < N);
+        let p = buf.as_mut_ptr() as *mut u8;
+        core::ptr::write_bytes(p.add(len), 0, N - len);
+        buf.assume_init_mut()
+    } //assume_init_with_len
+
+    /// safe version of [zstr::assume_init_with_len].  The buffer is
+    /// zeroed, then `reader` is given its first N-1 bytes to fill and
+    /// returns the number of bytes written.  The contents are cut at the
+    /// first zero byte and must be valid utf8, otherwise the string is left
+    /// empty and the utf8 error is returned.
+    /// ```
+    ///  use no_std_strings::zstr;
+    ///  let mut buf = zstr::<16>::uninit();
+    ///  let s = zstr::init_from_reader(&mut buf, |dst| {
+    ///    dst[..5].copy_from_slice(b"hello");
+    ///    5
+    ///  }).unwrap();
+    ///  assert_eq!(*s, "hello");
+    ///  assert!(zstr::<16>::init_from_reader(&mut zstr::uninit(), |dst| { dst[0] = 0xff; 1 }).is_err());
+    /// ```
+    pub fn init_from_reader<F>(buf: &mut MaybeUninit<zstr<N>>, reader: F) -> Result<&mut zstr<N>, core::str::Utf8Error>
+    where
+        F: FnOnce(&mut [u8]) -> usize,
+    {
+        // zeroed in place, to avoid building an N-byte temporary
+        let z = unsafe {
+            core::ptr::write_bytes(buf.as_mut_ptr() as *mut u8, 0, N);
+            buf.assume_init_mut()
+        };
+        let n = min(reader(&mut z.chrs[..N - 1]), N - 1);
+        let n = z.chrs[..n].iter().position(|b| *b == 0).unwrap_or(n);
+        if let Err(e) = core::str::from_utf8(&z.chrs[..n]) {
+            z.chrs.fill(0);
+            return Err(e);
+        }
+        z.chrs[n..].fill(0);
+        Ok(z)
+    } //init_from_reader
+
 
 
     /// length of the string in bytes (consistent with [str::len]).
@@ -121,6 +286,49 @@ impl<const N: usize> zstr<N> {
         N - 1
     }
 
+    /// returns a raw pointer to the start of the buffer, so that data can
+    /// be written into the string directly, for example as the target of a
+    /// UART or SPI DMA receive descriptor.  The contract is:
+    ///  - at most [zstr::capacity_bytes] bytes may be written, starting
+    ///    at the pointer; the last byte of the buffer is reserved for the
+    ///    terminating zero.
+    ///  - the string must not be read while the transfer is in progress.
+    ///  - once the data is complete, it must be committed with
+    ///    [zstr::set_len], after which the zstr is a valid string again.
+    /// ```
+    ///  use no_std_strings::zstr;
+    ///  let mut rx = zstr::<16>::new();
+    ///  let (ptr, cap) = (rx.as_mut_ptr(), rx.capacity_bytes());
+    ///  assert_eq!(cap, 15);
+    ///  // ... peripheral writes 4 bytes ...
+    ///  unsafe {
+    ///    core::ptr::copy_nonoverlapping(b"+OK\r".as_ptr(), ptr, 4);
+    ///    rx.set_len(4);
+    ///  }
+    ///  assert_eq!(rx, "+OK\r");
+    /// ```
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.chrs.as_mut_ptr()
+    }
+
+    /// number of bytes that may be written through [zstr::as_mut_ptr],
+    /// which is N-1.
+    pub const fn capacity_bytes(&self) -> usize {
+        N - 1
+    }
+
+    /// sets the length of the string to n bytes by writing the terminating
+    /// zero at position n, committing data written through
+    /// [zstr::as_mut_ptr].
+    ///
+    /// # Safety
+    /// n must be less than N, and the first n bytes of the buffer must be
+    /// valid utf8 that contains no zero byte.
+    pub unsafe fn set_len(&mut self, n: usize) {
+        debug_assert!(n < N);
+        *self.chrs.get_unchecked_mut(n) = 0;
+    }
+
     // returns the byte length of the string, which will be less than N
     fn blen(&self) -> usize {
         let mut i = 0;
@@ -224,6 +432,21 @@ impl<const N: usize> zstr<N> {
         self.chrs[n] as char
     }
 
+    /// returns the byte at position i of the string, or None if i is not
+    /// less than the length of the string.  Unlike `s[i]`, which can
+    /// access any byte of the underlying array, this only returns bytes of
+    /// the string itself.
+    /// ```
+    ///  use no_std_strings::zstr;
+    ///  let s = zstr::<8>::from("$OK");
+    ///  assert_eq!(s.get_byte(0), Some(b'$'));
+    ///  assert_eq!(s.get_byte(3), None);
+    ///  assert_eq!(s[3], 0);
+    /// ```
+    pub fn get_byte(&self, i: usize) -> Option<u8> {
+        self.to_str().as_bytes().get(i).copied()
+    }
+
     /// determines if string is an ascii string
     pub fn is_ascii(&self) -> bool {
         self.as_str().is_ascii()
@@ -233,20 +456,28 @@ impl<const N: usize> zstr<N> {
     /// a *character* position to truncate up to, not the byte position.
     /// If n is greater than the
     /// current character length of the string, this operation will have no effect.
-    pub fn truncate(&mut self, n: usize) // n is char position, not binary position
+    /// Returns the number of bytes removed.
+    pub fn truncate(&mut self, n: usize) -> usize // n is char position, not binary position
     {
+        let blen = self.blen();
         if let Some((bi, c)) = self.as_str().char_indices().nth(n) {
             self.chrs[bi] = 0;
+            return blen - bi;
         }
+        0
     }
     
     /// truncates string up to *byte* position n.  **Panics** if n is
-    /// not on a character boundary, similar to truncate on owned strings
-    pub fn truncate_bytes(&mut self, n: usize) {
-         if n<N {
+    /// not on a character boundary, similar to truncate on owned strings.
+    /// Returns the number of bytes removed.
+    pub fn truncate_bytes(&mut self, n: usize) -> usize {
+         let blen = self.blen();
+         if n<blen {
            assert!(self.is_char_boundary(n));
     	   self.chrs[n] = 0;
+           return blen - n;
 	 }
+         0
     }
     
     /// resets string to empty string
@@ -301,6 +532,27 @@ impl<const N: usize> zstr<N> {
 
 } //impl zstr<N>
 
+/// Error returned by [zstr::from_bytes_until_nul]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FromBytesUntilNulError {
+    /// the slice contains no zero byte
+    NulNotFound,
+    /// the bytes before the first zero are not valid utf8
+    InvalidUtf8(core::str::Utf8Error),
+    /// the bytes before the first zero do not fit in the zstr
+    ExceedsCapacity,
+}
+
+impl core::fmt::Display for FromBytesUntilNulError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FromBytesUntilNulError::NulNotFound => f.write_str("no nul terminator found"),
+            FromBytesUntilNulError::InvalidUtf8(e) => write!(f, "invalid utf8: {}", e),
+            FromBytesUntilNulError::ExceedsCapacity => f.write_str("string exceeds capacity"),
+        }
+    }
+}
+
 impl<const N:usize> core::ops::Deref for zstr<N>
 {
     type Target = str;
@@ -354,7 +606,8 @@ impl<const N: usize> core::cmp::Ord for zstr<N> {
 impl<const M: usize> zstr<M> {
     /// converts an zstr\<M\> to an zstr\<N\>. If the length of the string being
     /// converted is greater than N, the extra characters are ignored.
-    /// This operation produces a copy.
+    /// This operation produces a copy.  If truncated, the string is cut at
+    /// a character boundary.
     /// Example:
     ///```ignore
     ///  let s1:zstr<8> = zstr::from("abcdefg");
@@ -362,7 +615,8 @@ impl<const M: usize> zstr<M> {
     ///```
     pub fn resize<const N: usize>(&self) -> zstr<N> {
         let slen = self.blen();
-        let length = if (slen < N - 1) { slen } else { N - 1 };
+        let mut length = if (slen < N - 1) { slen } else { N - 1 };
+        while !self.is_char_boundary(length) { length -= 1; }
         let mut chars = [0u8; N];
         chars[..length].clone_from_slice(&self.chrs[..length]);
         //for i in 0..length {chars[i] = self.chrs[i];}
@@ -377,11 +631,46 @@ impl<const M: usize> zstr<M> {
             None
         }
     }
+
+    /// version of resize that returns an error instead of truncating.
+    /// ```
+    ///  use no_std_strings::{zstr, CapacityError};
+    ///  let s = zstr::<16>::from("config-v2");
+    ///  assert_eq!(s.resize_checked::<10>().unwrap(), "config-v2");
+    ///  assert_eq!(s.resize_checked::<8>(), Err(CapacityError));
+    /// ```
+    pub fn resize_checked<const N: usize>(&self) -> Result<zstr<N>, CapacityError> {
+        self.reallocate().ok_or(CapacityError)
+    }
+
+    /// version of resize that also returns the number of bytes dropped,
+    /// which is 0 if the string fits.
+    /// ```
+    ///  use no_std_strings::zstr;
+    ///  let s = zstr::<16>::from("config-é");
+    ///  let (t, dropped) = s.resize_reporting::<9>();
+    ///  assert_eq!((t.as_str(), dropped), ("config-", 2));
+    ///  assert_eq!(s.resize_reporting::<10>().1, 0);
+    /// ```
+    pub fn resize_reporting<const N: usize>(&self) -> (zstr<N>, usize) {
+        let z = self.resize();
+        let dropped = self.len() - z.len();
+        (z, dropped)
+    }
 } //impl zstr<M>
 
+/// Display and Debug use [core::fmt::Formatter::pad], so that width,
+/// alignment and precision flags behave as for `str` and for the other
+/// string types.
+/// ```
+///  use no_std_strings::{zstr, str8, str_format, str32};
+///  let (z, t) = (zstr::<8>::from("ab"), str8::from("ab"));
+///  assert_eq!(str_format!(str32, "[{:>4}|{:<4}]", z, t), "[  ab|ab  ]");
+///  assert_eq!(str_format!(str32, "[{:^6?}|{:.1}]", z, t), "[  ab  |a]");
+/// ```
 impl<const N: usize> core::fmt::Display for zstr<N> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}", self.as_str())
+        f.pad(self.to_str())
     }
 }
 
@@ -390,30 +679,88 @@ impl<const N: usize> PartialEq<&str> for zstr<N> {
         self.as_str() == *other // see below
     } //eq
 }
-impl<const N: usize> PartialEq<&str> for &zstr<N> {
-    fn eq(&self, other: &&str) -> bool {
-        &self.as_str() == other
-        /*
-          let obytes = other.as_bytes();
-          let olen = obytes.len();
-          let blen = self.blen();
-          if olen!=blen {return false;}
-          for i in 0..olen
-          {
-             if obytes[i] != self.chrs[i] {return false;}
-          }
-          return true;
-        */
-    } //eq
-}
 impl<'t, const N: usize> PartialEq<zstr<N>> for &'t str {
     fn eq(&self, other: &zstr<N>) -> bool {
         &other.as_str() == self
     }
 }
-impl<'t, const N: usize> PartialEq<&zstr<N>> for &'t str {
-    fn eq(&self, other: &&zstr<N>) -> bool {
-        &other.as_str() == self
+/// comparisons with unsized `str`, and with `String` and `Cow<str>`
+/// under `--features alloc`
+/// ```
+///  use no_std_strings::{zstr, str8};
+///  fn same<T: PartialEq<str> + ?Sized>(a: &T, b: &str) -> bool { a == b }
+///  assert!(same(&zstr::<8>::from("abc"), "abc"));
+///  assert!(same(&str8::from("abc"), "abc"));
+///  assert!(*"abc" == zstr::<8>::from("abc"));
+/// ```
+impl<const N: usize> PartialEq<str> for zstr<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.to_str() == other
+    }
+}
+impl<const N: usize> PartialEq<zstr<N>> for str {
+    fn eq(&self, other: &zstr<N>) -> bool {
+        other.to_str() == self
+    }
+}
+/// requires `--features alloc`
+/// ```
+///  use no_std_strings::zstr;
+///  use std::borrow::Cow;
+///  let z = zstr::<8>::from("abc");
+///  assert!(z == String::from("abc") && String::from("abc") == z);
+///  assert!(z != String::from("abd") && String::from("ab") != z);
+///  assert!(z == Cow::Borrowed("abc") && Cow::<str>::Owned("abc".into()) == z);
+///  assert!(z != Cow::Borrowed("x") && Cow::Borrowed("x") != z);
+/// ```
+#[cfg(feature = "alloc")]
+impl<const N: usize> PartialEq<alloc::string::String> for zstr<N> {
+    fn eq(&self, other: &alloc::string::String) -> bool {
+        self.to_str() == other.as_str()
+    }
+}
+#[cfg(feature = "alloc")]
+impl<const N: usize> PartialEq<zstr<N>> for alloc::string::String {
+    fn eq(&self, other: &zstr<N>) -> bool {
+        other.to_str() == self.as_str()
+    }
+}
+#[cfg(feature = "alloc")]
+impl<'t, const N: usize> PartialEq<alloc::borrow::Cow<'t, str>> for zstr<N> {
+    fn eq(&self, other: &alloc::borrow::Cow<'t, str>) -> bool {
+        self.to_str() == other.as_ref()
+    }
+}
+#[cfg(feature = "alloc")]
+impl<'t, const N: usize> PartialEq<zstr<N>> for alloc::borrow::Cow<'t, str> {
+    fn eq(&self, other: &zstr<N>) -> bool {
+        other.to_str() == self.as_ref()
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<tstr<M>> for zstr<N> {
+    fn eq(&self, other: &tstr<M>) -> bool {
+        other.to_str() == self.to_str()
+    }
+}
+impl<const N: usize, const M: usize> PartialEq<&tstr<M>> for zstr<N> {
+    fn eq(&self, other: &&tstr<M>) -> bool {
+        other.to_str() == self.to_str()
+    }
+}
+
+/// compares contents byte-wise, so that zstr and tstr strings can be
+/// ordered together regardless of capacity.
+/// ```
+///  use no_std_strings::{zstr, str8, str16};
+///  let z = zstr::<8>::from("abc");
+///  assert!(z < str16::from("abd"));
+///  assert!(str8::from("ab") < z);
+///  assert_eq!(z, str8::from("abc"));
+/// ```
+impl<const N: usize, const M: usize> PartialOrd<tstr<M>> for zstr<N> {
+    fn partial_cmp(&self, other: &tstr<M>) -> Option<Ordering> {
+        Some(self.to_str().as_bytes().cmp(other.as_bytes()))
     }
 }
 
@@ -487,6 +834,122 @@ impl<const N: usize> core::fmt::Write for zstr<N> {
     } //write_str
 } //core::fmt::Write trait
 
+/// A writer that overwrites a zstr in place from a seekable byte
+/// position, so that fields of a fixed-layout line can be updated
+/// without reformatting the whole line.  Writing past the end extends
+/// the string, and seeking past the end pads it with spaces on the next
+/// write.  Multibyte characters partly overwritten are replaced by
+/// spaces so the string remains valid utf8.  If a write exceeds
+/// capacity, the characters that fit are written and an error is
+/// returned; if none fit, the string is left unchanged.  Text containing a `'\0'`, which would end the string, is
+/// rejected with an error and nothing is written.
+/// ```
+///  use no_std_strings::{zstr, Cursor};
+///  use core::fmt::Write;
+///  let mut line = zstr::<32>::from("T=--.- H=--% OK");
+///  let mut c = Cursor::new(&mut line);
+///  c.seek(2).unwrap();
+///  write!(c, "{:4.1}", 21.5).unwrap();
+///  c.seek(9).unwrap();
+///  write!(c, "{:2}", 40).unwrap();
+///  c.seek(18).unwrap();
+///  c.write_str("λ").unwrap();
+///  assert_eq!(line, "T=21.5 H=40% OK   λ");
+///  let mut s = zstr::<8>::from("aéb");
+///  let mut c = Cursor::new(&mut s);
+///  c.seek(2).unwrap();
+///  c.write_str("x").unwrap();
+///  assert!(c.write_str("yzwvu").is_err());
+///  assert_eq!(s, "a xyzwv");
+///  let mut s = zstr::<8>::from("abcdef");
+///  assert!(Cursor::new(&mut s).write_str("x\0y").is_err());
+///  assert_eq!(s, "abcdef");
+///  let mut s = zstr::<8>::from("ab");
+///  let mut c = Cursor::new(&mut s);
+///  c.seek(6).unwrap();
+///  assert!(c.write_str("éx").is_err());
+///  assert_eq!(s, "ab");
+/// ```
+pub struct Cursor<'a, const N: usize> {
+    z: &'a mut zstr<N>,
+    pos: usize,
+}
+
+impl<'a, const N: usize> Cursor<'a, N> {
+    /// creates a cursor at position 0
+    pub fn new(z: &'a mut zstr<N>) -> Self {
+        Cursor { z, pos: 0 }
+    }
+
+    /// moves to byte position pos, which must be less than the capacity
+    /// N-1 of the string
+    pub fn seek(&mut self, pos: usize) -> Result<(), CapacityError> {
+        if pos >= N - 1 {
+            return Err(CapacityError);
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    /// the current byte position
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// returns the string being written
+    pub fn into_inner(self) -> &'a mut zstr<N> {
+        self.z
+    }
+} //impl Cursor
+
+impl<const N: usize> core::fmt::Write for Cursor<'_, N> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        if s.contains('\0') {
+            return Err(core::fmt::Error);
+        }
+        let len = self.z.len();
+        let chrs = &mut self.z.chrs;
+        let is_cont = |b: u8| b & 0xc0 == 0x80;
+        let mut n = min(s.len(), N - 1 - self.pos);
+        while !s.is_char_boundary(n) {
+            n -= 1;
+        }
+        if n == 0 && !s.is_empty() {
+            return Err(core::fmt::Error);
+        }
+        if self.pos > len {
+            chrs[len..self.pos].fill(b' ');
+        } else if self.pos < len {
+            // blank the start of a character cut by the write
+            let mut k = self.pos;
+            if is_cont(chrs[k]) {
+                loop {
+                    k -= 1;
+                    let b = chrs[k];
+                    chrs[k] = b' ';
+                    if !is_cont(b) {
+                        break;
+                    }
+                }
+            }
+        }
+        let end = self.pos + n;
+        chrs[self.pos..end].copy_from_slice(&s.as_bytes()[..n]);
+        if end >= len {
+            chrs[end] = 0;
+        } else {
+            // blank the rest of a character cut by the write
+            let mut k = end;
+            while is_cont(chrs[k]) {
+                chrs[k] = b' ';
+                k += 1;
+            }
+        }
+        self.pos = end;
+        if n < s.len() { Err(core::fmt::Error) } else { Ok(()) }
+    } //write_str
+}
+
 
 
 /*